@@ -1,6 +1,7 @@
 use std::error::Error;
 
 pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
     todo!("Implement part 1");
 }
 
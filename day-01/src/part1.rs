@@ -9,6 +9,7 @@ pub fn extract_number_literal(input: &str) -> Result<char, Box<dyn Error>> {
 }
 
 pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
     let mut sum: u32 = 0;
     for line in input.lines() {
         if !line.is_empty() {
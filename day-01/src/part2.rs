@@ -188,6 +188,7 @@ pub fn extract_number_pair(input: &str) -> String {
 }
 
 pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
     let mut sum = 0;
 
     for line in input.lines() {
@@ -64,6 +64,7 @@ impl Bag {
 }
 
 pub fn run(input: &str) -> Result<u32, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
     let real_bag = Bag::new(12, 13, 14);
 
     let sum: u32 = input
@@ -74,7 +75,7 @@ pub fn run(input: &str) -> Result<u32, Box<dyn Error>> {
             let game_label = game_parts.first().expect("should have a game label");
             let game_id = game_label
                 .split(' ')
-                .last()
+                .next_back()
                 .expect("should have game id")
                 .parse::<u32>()
                 .expect("should be numeric game id");
@@ -34,7 +34,6 @@ impl GameRound {
 
 #[derive(Debug)]
 pub struct GameDetails {
-    game_id: u32,
     bag: Bag,
 }
 
@@ -42,18 +41,9 @@ impl GameDetails {
     pub fn from(game_record: &str) -> GameDetails {
         let game_parts: Vec<&str> = game_record.split(':').collect();
 
-        let game_label = game_parts.first().expect("should have a game label");
-        let game_id = game_label
-            .split(' ')
-            .last()
-            .expect("should have game id")
-            .parse::<u32>()
-            .expect("should be numeric game id");
-
         let game_details = game_parts.last().expect("should have game details");
 
         GameDetails {
-            game_id,
             bag: Bag::from(game_details),
         }
     }
@@ -93,6 +83,7 @@ impl Bag {
 }
 
 pub fn run(input: &str) -> Result<u32, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
     let sum: u32 = input
         .lines()
         .map(|line| {
@@ -132,7 +132,8 @@ impl EngineData {
 }
 
 pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
-    let data = EngineData::from(input);
+    let input = normalize::input::grid(input, '.').warn();
+    let data = EngineData::from(&input);
     let mut sum: i32 = 0;
 
     data.numbers.iter().for_each(|number| {
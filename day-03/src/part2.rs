@@ -137,12 +137,13 @@ impl EngineData {
 }
 
 pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
-    let data = EngineData::from(input);
+    let input = normalize::input::grid(input, '.').warn();
+    let data = EngineData::from(&input);
     let mut sum: i32 = 0;
 
     data.symbols
         .iter()
-        .filter(|s| s.value == "*".to_string())
+        .filter(|s| s.value == "*")
         .for_each(|symbol| {
             let pos = &symbol.position;
             let origin = EnginePosition::new(pos.x - 1, pos.y - 1);
@@ -265,5 +266,14 @@ mod tests {
             .iter()
             .map(|symbol| (symbol.position.x, symbol.position.y))
             .collect();
+
+        assert_eq!(
+            vec![467, 114, 35, 633, 617, 58, 592, 755, 664, 598],
+            numbers
+        );
+        assert_eq!(
+            vec![(3, 1), (6, 3), (3, 4), (5, 5), (3, 8), (5, 8)],
+            symbol_positions
+        );
     }
 }
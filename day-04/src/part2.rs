@@ -121,7 +121,8 @@ impl CardPile {
 }
 
 pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
-    let pile = CardPile::from(input);
+    let input = normalize::input::text(input).warn();
+    let pile = CardPile::from(&input);
 
     Ok(pile.copy_count.to_string())
 }
@@ -0,0 +1,473 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    error::Error,
+    fmt,
+    ops::Range,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlmanacError {
+    MissingSeeds,
+    MalformedSeeds(String),
+    MalformedHeader(String),
+    MalformedEntry(String),
+    EntryOutsideMap(String),
+    /// `start + length` of the entry on this line does not fit in a `u64`
+    EntryOverflow(usize),
+    Cycle(String),
+    Disconnected(String),
+    UnknownCategory(String),
+    NoPath {
+        source: String,
+        target: String,
+    },
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use AlmanacError::*;
+        match self {
+            MissingSeeds => write!(f, "almanac should start with a `seeds:` line"),
+            MalformedSeeds(line) => write!(f, "invalid seeds line: `{line}`"),
+            MalformedHeader(line) => write!(f, "invalid map header: `{line}`"),
+            MalformedEntry(line) => write!(f, "invalid map entry: `{line}`"),
+            EntryOutsideMap(line) => write!(f, "map entry before any map header: `{line}`"),
+            EntryOverflow(line) => write!(f, "map entry on line {line} overflows u64"),
+            Cycle(category) => write!(f, "category `{category}` is part of a cycle"),
+            Disconnected(category) => {
+                write!(f, "category `{category}` is not connected to the rest")
+            }
+            UnknownCategory(category) => write!(f, "unknown category `{category}`"),
+            NoPath { source, target } => write!(f, "no conversion from `{source}` to `{target}`"),
+        }
+    }
+}
+
+impl Error for AlmanacError {}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct SeedMapEntry {
+    pub dst_start: u64,
+    pub src_start: u64,
+    pub length: u64,
+    /// 1-based input line, or 0 if not parsed from input
+    pub line: usize,
+}
+
+impl SeedMapEntry {
+    pub fn new(dst_start: u64, src_start: u64, length: u64) -> SeedMapEntry {
+        SeedMapEntry {
+            dst_start,
+            src_start,
+            length,
+            line: 0,
+        }
+    }
+
+    pub fn from(line: &str, line_number: usize) -> Result<SeedMapEntry, AlmanacError> {
+        let values: Vec<u64> = line
+            .split_whitespace()
+            .map(|value| value.parse::<u64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| AlmanacError::MalformedEntry(line.to_string()))?;
+
+        match values[..] {
+            [dst_start, src_start, length] => Ok(SeedMapEntry {
+                line: line_number,
+                ..SeedMapEntry::new(dst_start, src_start, length)
+            }),
+            _ => Err(AlmanacError::MalformedEntry(line.to_string())),
+        }
+    }
+
+    pub fn src_range(&self) -> Result<Range<u64>, AlmanacError> {
+        self.range(self.src_start)
+    }
+
+    pub fn dst_range(&self) -> Result<Range<u64>, AlmanacError> {
+        self.range(self.dst_start)
+    }
+
+    fn range(&self, start: u64) -> Result<Range<u64>, AlmanacError> {
+        let end = start
+            .checked_add(self.length)
+            .ok_or(AlmanacError::EntryOverflow(self.line))?;
+
+        Ok(start..end)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SeedMap {
+    pub source: String,
+    pub destination: String,
+    pub entries: Vec<SeedMapEntry>,
+    /// 1-based input line of the header, or 0 if not parsed from input
+    pub line: usize,
+}
+
+impl SeedMap {
+    pub fn new(source: &str, destination: &str) -> SeedMap {
+        SeedMap {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            entries: vec![],
+            line: 0,
+        }
+    }
+
+    /// Parses a `source-to-destination map:` header
+    pub fn from(header: &str, line_number: usize) -> Result<SeedMap, AlmanacError> {
+        let malformed = || AlmanacError::MalformedHeader(header.to_string());
+
+        let label = header.strip_suffix(" map:").ok_or_else(malformed)?;
+        let (source, destination) = label.split_once("-to-").ok_or_else(malformed)?;
+
+        if source.is_empty() || destination.is_empty() {
+            return Err(malformed());
+        }
+
+        Ok(SeedMap {
+            line: line_number,
+            ..SeedMap::new(source, destination)
+        })
+    }
+
+    pub fn next_id(&self, id: u64) -> Option<u64> {
+        if let Some(entry) = self.entries.iter().find(|entry| {
+            // find the entry that can contain the id, without overflowing at the top of `u64`
+            id >= entry.src_start && id - entry.src_start < entry.length
+        }) {
+            // return destination plus offset
+            let new_id = entry.dst_start + (id - entry.src_start);
+            return Some(new_id);
+        }
+        // default to id if no matching map range
+        Some(id)
+    }
+}
+
+///
+/// If You Give A Seed A Fertilizer
+///
+/// - an almanac lists seeds followed by any number of `source-to-destination map:` sections
+/// - categories are read from the section headers instead of a fixed list
+/// - sections form a graph of category transitions that must be a connected DAG
+/// - any reachable `source -> target` conversion can be requested (e.g. soil -> humidity)
+///
+#[derive(Clone, Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<SeedMap>,
+    /// 1-based input line of the `seeds:` list
+    pub seeds_line: usize,
+}
+
+impl Almanac {
+    pub fn parse(input: &str) -> Result<Almanac, AlmanacError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.is_empty());
+
+        // parse seed id list
+        let (seeds_line, seeds) = lines
+            .next()
+            .and_then(|(i, line)| Some((i, line.strip_prefix("seeds:")?)))
+            .ok_or(AlmanacError::MissingSeeds)?;
+        let seeds: Vec<u64> = seeds
+            .split_whitespace()
+            .map(|seed| seed.parse::<u64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| AlmanacError::MalformedSeeds(seeds.to_string()))?;
+
+        // loop through and build maps
+        let mut maps: Vec<SeedMap> = vec![];
+        for (i, line) in lines {
+            if line.starts_with(|c: char| c.is_ascii_digit()) {
+                let entry = SeedMapEntry::from(line, i)?;
+                maps.last_mut()
+                    .ok_or_else(|| AlmanacError::EntryOutsideMap(line.to_string()))?
+                    .entries
+                    .push(entry);
+            } else {
+                maps.push(SeedMap::from(line, i)?);
+            }
+        }
+
+        let almanac = Almanac {
+            seeds,
+            maps,
+            seeds_line,
+        };
+        almanac.validate_graph()?;
+
+        Ok(almanac)
+    }
+
+    /// Every category named in a map header, in order of first appearance
+    pub fn categories(&self) -> Vec<&str> {
+        self.maps
+            .iter()
+            .flat_map(|map| [map.source.as_str(), map.destination.as_str()])
+            .fold(vec![], |mut acc, category| {
+                if !acc.contains(&category) {
+                    acc.push(category);
+                }
+                acc
+            })
+    }
+
+    /// Checks that the category transitions form a single connected DAG
+    pub fn validate_graph(&self) -> Result<(), AlmanacError> {
+        let categories = self.categories();
+        let Some(first) = categories.first() else {
+            return Ok(());
+        };
+
+        // Kahn's algorithm, anything left over is on a cycle
+        let mut in_degrees: BTreeMap<&str, usize> =
+            categories.iter().map(|&category| (category, 0)).collect();
+        self.maps.iter().for_each(|map| {
+            *in_degrees.get_mut(map.destination.as_str()).unwrap() += 1;
+        });
+
+        let mut queue: VecDeque<&str> = in_degrees
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&category, _)| category)
+            .collect();
+
+        while let Some(category) = queue.pop_front() {
+            for map in self.maps.iter().filter(|map| map.source == category) {
+                let degree = in_degrees.get_mut(map.destination.as_str()).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(&map.destination);
+                }
+            }
+        }
+
+        if let Some((category, _)) = in_degrees.iter().find(|(_, &degree)| degree > 0) {
+            return Err(AlmanacError::Cycle(category.to_string()));
+        }
+
+        // ignore edge direction when checking that everything is connected
+        let mut seen: BTreeSet<&str> = BTreeSet::from([*first]);
+        let mut queue: VecDeque<&str> = VecDeque::from([*first]);
+
+        while let Some(category) = queue.pop_front() {
+            for map in &self.maps {
+                let neighbour = if map.source == category {
+                    &map.destination
+                } else if map.destination == category {
+                    &map.source
+                } else {
+                    continue;
+                };
+                if seen.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        match categories.iter().find(|category| !seen.contains(*category)) {
+            Some(category) => Err(AlmanacError::Disconnected(category.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Finds the shortest chain of maps converting `source` ids into `target` ids
+    pub fn path(&self, source: &str, target: &str) -> Result<Vec<&SeedMap>, AlmanacError> {
+        let categories = self.categories();
+        for category in [source, target] {
+            if !categories.contains(&category) {
+                return Err(AlmanacError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        // breadth-first search, remembering which map reached each category
+        let mut via: BTreeMap<&str, Option<&SeedMap>> = BTreeMap::from([(source, None)]);
+        let mut queue: VecDeque<&str> = VecDeque::from([source]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == target {
+                let mut path = vec![];
+                let mut current = target;
+                while let Some(Some(map)) = via.get(current) {
+                    path.push(*map);
+                    current = &map.source;
+                }
+                path.reverse();
+
+                return Ok(path);
+            }
+
+            for map in self.maps.iter().filter(|map| map.source == category) {
+                if !via.contains_key(map.destination.as_str()) {
+                    via.insert(&map.destination, Some(map));
+                    queue.push_back(&map.destination);
+                }
+            }
+        }
+
+        Err(AlmanacError::NoPath {
+            source: source.to_string(),
+            target: target.to_string(),
+        })
+    }
+
+    /// Seeds read as `start length` pairs, `None` if a range ends past `u64::MAX`
+    pub fn seed_ranges(&self) -> Option<Vec<Range<u64>>> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| Some(pair[0]..pair[0].checked_add(pair[1])?))
+            .collect()
+    }
+
+    /// Converts a `source` id into its `target` id
+    pub fn convert(&self, source: &str, target: &str, id: u64) -> Result<u64, AlmanacError> {
+        Ok(self
+            .path(source, target)?
+            .iter()
+            .fold(id, |acc, map| map.next_id(acc).unwrap_or(acc)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn parses_categories_from_headers() {
+        let almanac = Almanac::parse(EXAMPLE).expect("should parse almanac");
+
+        assert_eq!(
+            vec![
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ],
+            almanac.categories()
+        );
+    }
+
+    #[test]
+    fn converts_between_any_categories() {
+        let almanac = Almanac::parse(EXAMPLE).expect("should parse almanac");
+
+        // seed 79 -> soil 81 -> ... -> humidity 78 -> location 82
+        assert_eq!(Ok(82), almanac.convert("seed", "location", 79));
+        assert_eq!(Ok(78), almanac.convert("soil", "humidity", 81));
+        assert_eq!(Ok(81), almanac.convert("seed", "seed", 81));
+        assert_eq!(
+            Err(AlmanacError::NoPath {
+                source: "location".to_string(),
+                target: "seed".to_string()
+            }),
+            almanac.convert("location", "seed", 82)
+        );
+    }
+
+    #[test]
+    fn unknown_headers_start_new_maps() {
+        let input = "seeds: 1
+
+seed-to-soil map:
+10 0 5
+
+soil-to-moss map:
+20 10 5";
+
+        let almanac = Almanac::parse(input).expect("should parse almanac");
+
+        assert_eq!(2, almanac.maps.len());
+        assert_eq!(Ok(21), almanac.convert("seed", "moss", 1));
+    }
+
+    #[test]
+    fn maps_ids_up_to_the_end_of_an_entry() {
+        let mut map = SeedMap::new("seed", "soil");
+        map.entries.push(SeedMapEntry::new(50, 98, 2));
+
+        assert_eq!(Some(97), map.next_id(97));
+        assert_eq!(Some(50), map.next_id(98));
+        assert_eq!(Some(51), map.next_id(99));
+        // `src_start + length` is the first id past the entry
+        assert_eq!(Some(100), map.next_id(100));
+    }
+
+    #[test]
+    fn reports_overflowing_ranges() {
+        let entry = SeedMapEntry::from("0 18446744073709551610 10", 9).expect("should parse entry");
+
+        assert_eq!(Ok(0..10), entry.dst_range());
+        assert_eq!(Err(AlmanacError::EntryOverflow(9)), entry.src_range());
+
+        let mut map = SeedMap::new("seed", "soil");
+        map.entries.push(entry);
+        assert_eq!(Some(5), map.next_id(u64::MAX));
+    }
+
+    #[test]
+    fn rejects_invalid_graphs() {
+        let cycle = "seeds: 1
+
+a-to-b map:
+b-to-a map:";
+        let disconnected = "seeds: 1
+
+a-to-b map:
+c-to-d map:";
+
+        assert_eq!(
+            AlmanacError::Cycle("a".to_string()),
+            Almanac::parse(cycle).unwrap_err()
+        );
+        assert_eq!(
+            AlmanacError::Disconnected("c".to_string()),
+            Almanac::parse(disconnected).unwrap_err()
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod almanac;
 pub mod part1;
 pub mod part2;
 pub mod part2_a;
+pub mod validate;
@@ -0,0 +1,286 @@
+use crate::almanac::{Almanac, SeedMapEntry};
+use std::{error::Error, fmt};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// How the `seeds:` list is read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedMode {
+    /// Every value is a seed
+    Single,
+    /// Values pair up as `start length` ranges
+    Ranges,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticKind {
+    /// Two source ranges in the same map claim the same ids
+    OverlappingEntries { map: String, other_line: usize },
+    /// `start + length` does not fit in a `u64`
+    Overflow { map: String },
+    /// Ids between two source ranges that pass through unchanged
+    Gap { map: String, start: u64, end: u64 },
+    /// Range mode pairs seeds up, so a trailing value would be dropped
+    OddSeedCount(usize),
+    /// A seed range's `start + length` does not fit in a `u64`
+    SeedRangeOverflow { start: u64, length: u64 },
+    /// The same `source-to-destination` header appears more than once
+    DuplicateHeader { map: String, first_line: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DiagnosticKind::*;
+
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "line {}: {severity}: ", self.line)?;
+
+        match &self.kind {
+            OverlappingEntries { map, other_line } => {
+                write!(f, "`{map}` entry overlaps entry on line {other_line}")
+            }
+            Overflow { map } => write!(f, "`{map}` entry overflows u64"),
+            Gap { map, start, end } => write!(f, "`{map}` has no entry for ids {start}..{end}"),
+            OddSeedCount(count) => write!(f, "odd number of seeds ({count}) for range mode"),
+            SeedRangeOverflow { start, length } => {
+                write!(f, "seed range {start} + {length} overflows u64")
+            }
+            DuplicateHeader { map, first_line } => {
+                write!(f, "`{map}` already declared on line {first_line}")
+            }
+        }
+    }
+}
+
+/// Every error found while validating an almanac, plus any warnings
+#[derive(Debug)]
+pub struct ValidationError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.diagnostics.iter().map(|d| d.to_string()).collect();
+        write!(f, "invalid almanac:\n{}", lines.join("\n"))
+    }
+}
+
+impl Error for ValidationError {}
+
+fn overflows(entry: &SeedMapEntry) -> bool {
+    entry.src_start.checked_add(entry.length).is_none()
+        || entry.dst_start.checked_add(entry.length).is_none()
+}
+
+///
+/// Collects diagnostics for an almanac, sorted by line
+///
+/// - errors: overlapping source ranges, `u64` overflow, duplicate headers
+/// - warnings: gaps between source ranges
+/// - errors in [`SeedMode::Ranges`] only, where seeds pair up: an odd seed
+///   count or a seed range overflowing `u64`
+/// - warnings: gaps between source ranges, normal in puzzle input since
+///   unmapped ids pass through, so `run` ignores them
+///
+pub fn validate(almanac: &Almanac, mode: SeedMode) -> Vec<Diagnostic> {
+    use DiagnosticKind::*;
+    use Severity::*;
+
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+
+    if mode == SeedMode::Ranges {
+        if !almanac.seeds.len().is_multiple_of(2) {
+            diagnostics.push(Diagnostic {
+                line: almanac.seeds_line,
+                severity: Error,
+                kind: OddSeedCount(almanac.seeds.len()),
+            });
+        }
+
+        for pair in almanac.seeds.chunks_exact(2) {
+            if pair[0].checked_add(pair[1]).is_none() {
+                diagnostics.push(Diagnostic {
+                    line: almanac.seeds_line,
+                    severity: Error,
+                    kind: SeedRangeOverflow {
+                        start: pair[0],
+                        length: pair[1],
+                    },
+                });
+            }
+        }
+    }
+
+    for (i, map) in almanac.maps.iter().enumerate() {
+        let label = format!("{}-to-{}", map.source, map.destination);
+
+        if let Some(first) = almanac.maps[..i]
+            .iter()
+            .find(|other| other.source == map.source && other.destination == map.destination)
+        {
+            diagnostics.push(Diagnostic {
+                line: map.line,
+                severity: Error,
+                kind: DuplicateHeader {
+                    map: label.clone(),
+                    first_line: first.line,
+                },
+            });
+        }
+
+        let mut entries: Vec<&SeedMapEntry> = vec![];
+        for entry in &map.entries {
+            if overflows(entry) {
+                diagnostics.push(Diagnostic {
+                    line: entry.line,
+                    severity: Error,
+                    kind: Overflow { map: label.clone() },
+                });
+            } else {
+                entries.push(entry);
+            }
+        }
+
+        // walk source ranges in order, comparing each with the furthest end so far
+        entries.sort_by_key(|entry| (entry.src_start, entry.line));
+        let mut furthest: Option<&SeedMapEntry> = None;
+
+        for entry in entries {
+            if let Some(prev) = furthest {
+                let prev_end = prev.src_start + prev.length;
+                if entry.src_start < prev_end {
+                    diagnostics.push(Diagnostic {
+                        line: entry.line.max(prev.line),
+                        severity: Error,
+                        kind: OverlappingEntries {
+                            map: label.clone(),
+                            other_line: entry.line.min(prev.line),
+                        },
+                    });
+                } else if entry.src_start > prev_end {
+                    diagnostics.push(Diagnostic {
+                        line: entry.line,
+                        severity: Warning,
+                        kind: Gap {
+                            map: label.clone(),
+                            start: prev_end,
+                            end: entry.src_start,
+                        },
+                    });
+                }
+
+                if entry.src_start + entry.length <= prev_end {
+                    continue;
+                }
+            }
+            furthest = Some(entry);
+        }
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    diagnostics
+}
+
+/// Validates an almanac, failing if any diagnostic is an error and returning the warnings otherwise
+pub fn check(almanac: &Almanac, mode: SeedMode) -> Result<Vec<Diagnostic>, ValidationError> {
+    let diagnostics = validate(almanac, mode);
+
+    if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        return Err(ValidationError { diagnostics });
+    }
+
+    Ok(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_problems_with_line_numbers() {
+        let input = "seeds: 79 14 55
+
+seed-to-soil map:
+50 98 2
+52 50 48
+0 60 5
+
+soil-to-fertilizer map:
+0 18446744073709551610 10
+0 0 10
+20 20 5
+
+seed-to-soil map:
+1 2 3";
+
+        let almanac = Almanac::parse(input).expect("should parse almanac");
+        let diagnostics: Vec<(usize, Severity)> = validate(&almanac, SeedMode::Single)
+            .iter()
+            .map(|d| (d.line, d.severity))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (6, Severity::Error),
+                (9, Severity::Error),
+                (11, Severity::Warning),
+                (13, Severity::Error),
+            ],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn real_input_has_no_errors() {
+        let input = include_str!("../input/input1.txt");
+        let almanac = Almanac::parse(input).expect("should parse almanac");
+
+        assert!(check(&almanac, SeedMode::Ranges).is_ok());
+    }
+
+    #[test]
+    fn rejects_unpaired_seed_ranges() {
+        let input = "seeds: 79 14 18446744073709551610 10 55
+
+seed-to-soil map:
+50 98 2";
+
+        let almanac = Almanac::parse(input).expect("should parse almanac");
+        let kinds = |mode| -> Vec<(Severity, DiagnosticKind)> {
+            validate(&almanac, mode)
+                .into_iter()
+                .map(|d| (d.severity, d.kind))
+                .collect()
+        };
+
+        assert!(kinds(SeedMode::Single).is_empty());
+        assert_eq!(
+            vec![
+                (Severity::Error, DiagnosticKind::OddSeedCount(5)),
+                (
+                    Severity::Error,
+                    DiagnosticKind::SeedRangeOverflow {
+                        start: 18446744073709551610,
+                        length: 10
+                    }
+                ),
+            ],
+            kinds(SeedMode::Ranges)
+        );
+        assert!(check(&almanac, SeedMode::Ranges).is_err());
+    }
+}
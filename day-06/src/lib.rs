@@ -1,2 +1,5 @@
+pub mod model;
 pub mod part1;
 pub mod part2;
+pub mod race;
+pub mod sheet;
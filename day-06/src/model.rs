@@ -0,0 +1,261 @@
+use crate::race::Race;
+use std::ops::RangeInclusive;
+
+///
+/// Boat physics used to decide how far a boat travels for a given hold time
+///
+/// The default `winning_range` is a numeric search which assumes the
+/// distance first rises and then falls as the hold time grows. Models that
+/// can solve for the window in closed form should override it.
+///
+pub trait RaceModel {
+    /// Distance travelled in a race of `time` ms after holding for `hold` ms
+    fn distance(&self, hold: u64, time: u64) -> u128;
+
+    /// Inclusive range of hold times that beat the race record, if any
+    fn winning_range(&self, race: &Race) -> Option<RangeInclusive<u64>> {
+        search_winning_range(self, race)
+    }
+}
+
+/// The puzzle's boats: one mm/ms of speed per ms held, no delays or costs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Standard;
+
+impl RaceModel for Standard {
+    fn distance(&self, hold: u64, time: u64) -> u128 {
+        Race::new(time, 0).distance(hold)
+    }
+
+    fn winning_range(&self, race: &Race) -> Option<RangeInclusive<u64>> {
+        race.winning_range()
+    }
+}
+
+///
+/// Configurable boat spec
+///
+/// - `acceleration`: mm/ms of speed gained per ms held
+/// - `max_speed`: optional cap on the speed reached while charging
+/// - `startup_delay`: ms after release before the boat starts moving
+/// - `charge_cost`: mm of distance lost per ms held
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoatSpec {
+    pub acceleration: u64,
+    pub max_speed: Option<u64>,
+    pub startup_delay: u64,
+    pub charge_cost: u64,
+}
+
+impl Default for BoatSpec {
+    fn default() -> BoatSpec {
+        BoatSpec {
+            acceleration: 1,
+            max_speed: None,
+            startup_delay: 0,
+            charge_cost: 0,
+        }
+    }
+}
+
+impl BoatSpec {
+    /// Solves `acceleration * hold^2 - b * hold + dist < 0` where
+    /// `b = acceleration * moving_time - charge_cost`, only valid without a speed cap
+    fn closed_form(&self, race: &Race) -> Option<Option<RangeInclusive<u64>>> {
+        let a = self.acceleration as u128;
+        let moving = race.time.saturating_sub(self.startup_delay) as u128;
+        let b = a.checked_mul(moving)?.checked_sub(self.charge_cost as u128);
+        let Some(b) = b.filter(|&b| b > 0) else {
+            // charging always costs more than it gains
+            return Some(None);
+        };
+
+        let four_a_dist = a.checked_mul(4)?.checked_mul(race.dist as u128)?;
+        let Some(disc) = b.checked_mul(b)?.checked_sub(four_a_dist) else {
+            return Some(None);
+        };
+        let root = disc.isqrt();
+
+        let wins = |hold: u128| {
+            u64::try_from(hold).is_ok_and(|hold| {
+                hold <= race.time && self.distance(hold, race.time) > race.dist as u128
+            })
+        };
+
+        // first guess at each root, then step onto the exact boundaries
+        let mut low = (b - root) / (2 * a);
+        let mut high = (b + root) / (2 * a);
+        while low > 0 && wins(low - 1) {
+            low -= 1;
+        }
+        while low <= high && !wins(low) {
+            low += 1;
+        }
+        while wins(high + 1) {
+            high += 1;
+        }
+        while high > low && !wins(high) {
+            high -= 1;
+        }
+
+        if low > high || !wins(low) {
+            return Some(None);
+        }
+
+        Some(Some(low as u64..=high as u64))
+    }
+}
+
+impl RaceModel for BoatSpec {
+    fn distance(&self, hold: u64, time: u64) -> u128 {
+        let charged = (self.acceleration as u128).saturating_mul(hold as u128);
+        let speed = self
+            .max_speed
+            .map_or(charged, |max_speed| charged.min(max_speed as u128));
+        let moving = time.saturating_sub(hold).saturating_sub(self.startup_delay) as u128;
+        let cost = (self.charge_cost as u128).saturating_mul(hold as u128);
+
+        speed.saturating_mul(moving).saturating_sub(cost)
+    }
+
+    fn winning_range(&self, race: &Race) -> Option<RangeInclusive<u64>> {
+        match self.max_speed {
+            None if self.acceleration > 0 => self
+                .closed_form(race)
+                .unwrap_or_else(|| search_winning_range(self, race)),
+            _ => search_winning_range(self, race),
+        }
+    }
+}
+
+/// Binary searches for the peak distance, then for the record on either side of it
+pub fn search_winning_range<M: RaceModel + ?Sized>(
+    model: &M,
+    race: &Race,
+) -> Option<RangeInclusive<u64>> {
+    let distance = |hold: u64| model.distance(hold, race.time);
+    let record = race.dist as u128;
+
+    // first hold time where the distance stops increasing
+    let (mut lo, mut hi) = (0, race.time);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if distance(mid + 1) > distance(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    let peak = lo;
+
+    if distance(peak) <= record {
+        return None;
+    }
+
+    // first winning hold time on the rising side
+    let (mut lo, mut hi) = (0, peak);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if distance(mid) > record {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    let low = lo;
+
+    // last winning hold time on the falling side
+    let (mut lo, mut hi) = (peak, race.time);
+    while lo < hi {
+        let mid = hi - (hi - lo) / 2;
+        if distance(mid) > record {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let high = lo;
+
+    Some(low..=high)
+}
+
+impl Race {
+    pub fn winning_range_with<M: RaceModel + ?Sized>(
+        &self,
+        model: &M,
+    ) -> Option<RangeInclusive<u64>> {
+        model.winning_range(self)
+    }
+
+    pub fn winning_options_with<M: RaceModel + ?Sized>(&self, model: &M) -> u64 {
+        self.winning_range_with(model)
+            .map_or(0, |range| range.end() - range.start() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(model: &impl RaceModel, race: &Race) -> Option<RangeInclusive<u64>> {
+        let wins: Vec<u64> = (0..=race.time)
+            .filter(|&hold| model.distance(hold, race.time) > race.dist as u128)
+            .collect();
+
+        Some(*wins.first()?..=*wins.last()?)
+    }
+
+    #[test]
+    fn default_spec_matches_standard_boats() {
+        let spec = BoatSpec::default();
+
+        for race in [Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)] {
+            assert_eq!(race.winning_range_with(&Standard), race.winning_range());
+            assert_eq!(race.winning_range_with(&spec), race.winning_range());
+            assert_eq!(search_winning_range(&spec, &race), race.winning_range());
+        }
+    }
+
+    #[test]
+    fn alternative_specs_match_brute_force() {
+        let specs = [
+            BoatSpec {
+                acceleration: 3,
+                ..Default::default()
+            },
+            BoatSpec {
+                max_speed: Some(8),
+                ..Default::default()
+            },
+            BoatSpec {
+                startup_delay: 4,
+                ..Default::default()
+            },
+            BoatSpec {
+                acceleration: 2,
+                max_speed: Some(20),
+                startup_delay: 1,
+                charge_cost: 3,
+            },
+            BoatSpec {
+                charge_cost: 100,
+                ..Default::default()
+            },
+        ];
+
+        for spec in specs {
+            for time in 0..40 {
+                for dist in [0, 9, 40, 120, 200] {
+                    let race = Race::new(time, dist);
+
+                    assert_eq!(
+                        brute_force(&spec, &race),
+                        race.winning_range_with(&spec),
+                        "{spec:?} {race:?}"
+                    );
+                }
+            }
+        }
+    }
+}
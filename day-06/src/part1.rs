@@ -1,57 +1,34 @@
-use std::{error::Error, iter::zip};
+use crate::sheet::{Kerning, RaceSheet};
+use std::{error::Error, fmt};
 
-#[derive(Debug)]
-pub struct Race {
-    time: f32,
-    dist: f32,
-}
-
-impl Race {
-    pub fn winning_options(&self) -> f32 {
-        // dist = time * x - x^2
-        let a = -1.0;
-        let b = self.time;
-        let c = -(self.dist + 1.0);
-        let disc = b * b - 4.0 * a * c;
-
-        let x1 = (-b + f32::sqrt(disc)) / (2.0 * a);
-        let x2 = (-b - f32::sqrt(disc)) / (2.0 * a);
+/// Multiplying the races' winning options together overflows a `u64`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsOverflow;
 
-        // inclusive
-        x2.floor() - x1.ceil() + 1.0
+impl fmt::Display for OptionsOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "product of winning options overflows u64")
     }
 }
 
-pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
-    let mut lines = input.lines();
-    let times: Vec<f32> = lines
-        .next()
-        .unwrap()
-        .split(' ')
-        .filter(|el| !el.is_empty())
-        .flat_map(|time| time.parse::<f32>())
-        .collect();
-
-    let dists: Vec<f32> = lines
-        .next()
-        .unwrap()
-        .split(' ')
-        .filter(|el| !el.is_empty())
-        .flat_map(|dist| dist.parse::<f32>())
-        .collect();
+impl Error for OptionsOverflow {}
 
-    let races: Vec<Race> = zip(times, dists)
-        .map(|(time, dist)| Race { time, dist })
-        .collect();
+pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
+    let sheet = RaceSheet::parse(&input, Kerning::Separate)?;
 
-    let options: f32 = races.iter().map(|race| race.winning_options()).product();
+    let options = sheet
+        .races
+        .iter()
+        .try_fold(1u64, |acc, race| acc.checked_mul(race.winning_options()))
+        .ok_or(OptionsOverflow)?;
 
-    Ok((options as u32).to_string())
+    Ok(options.to_string())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::run;
+    use super::{run, OptionsOverflow};
 
     #[test]
     fn example_input() {
@@ -62,4 +39,18 @@ Distance:  9  40  200";
 
         assert_eq!(expected, run(input).expect("should return expected value"));
     }
+
+    #[test]
+    fn reports_overflowing_product() {
+        // each race has 4294967299 winning options
+        let input = "Time:      4294967300 4294967300 4294967300
+Distance:  0 0 0";
+
+        let error = run(input).expect_err("should overflow");
+
+        assert_eq!(
+            Some(&OptionsOverflow),
+            error.downcast_ref::<OptionsOverflow>()
+        );
+    }
 }
@@ -0,0 +1,233 @@
+use std::ops::RangeInclusive;
+
+///
+/// Wait For It
+///
+/// - holding the button for `hold` ms gives a speed of `hold` mm/ms
+/// - the boat covers `hold * (time - hold)` mm in the remaining time
+/// - a hold time wins if that distance beats the record `dist`
+///
+/// Winning hold times are the integers strictly between the roots of
+/// `hold^2 - time * hold + dist = 0`. The roots are found with an integer
+/// square root and then nudged onto the exact boundary, so no precision
+/// is lost for large races.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub dist: u64,
+}
+
+/// Unsigned integer width a race can be solved in
+pub trait RaceInt: Clone + Ord {
+    /// Whether holding for `hold` beats `dist` in a race lasting `time`
+    fn wins(time: &Self, dist: &Self, hold: &Self) -> bool;
+    /// First guess at the lower root, `None` when the discriminant is negative
+    fn lower_root(time: &Self, dist: &Self) -> Option<Self>;
+    fn is_zero(&self) -> bool;
+    fn increment(&self) -> Self;
+    fn decrement(&self) -> Self;
+    fn half(&self) -> Self;
+    fn minus(&self, other: &Self) -> Self;
+}
+
+impl RaceInt for u64 {
+    fn wins(time: &u64, dist: &u64, hold: &u64) -> bool {
+        Race::new(*time, *dist).wins(*hold)
+    }
+
+    fn lower_root(time: &u64, dist: &u64) -> Option<u64> {
+        let time = *time as u128;
+        let disc = (time * time).checked_sub(4 * *dist as u128)?;
+        Some(((time - disc.isqrt()) / 2) as u64)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn increment(&self) -> u64 {
+        self + 1
+    }
+
+    fn decrement(&self) -> u64 {
+        self - 1
+    }
+
+    fn half(&self) -> u64 {
+        self / 2
+    }
+
+    fn minus(&self, other: &u64) -> u64 {
+        self - other
+    }
+}
+
+/// Inclusive range of hold times that beat the record `dist`, if any
+pub fn winning_range<T: RaceInt>(time: &T, dist: &T) -> Option<RangeInclusive<T>> {
+    // first guess at the lower root, then step onto the exact boundary
+    let mut low = T::lower_root(time, dist)?;
+    while !low.is_zero() && T::wins(time, dist, &low.decrement()) {
+        low = low.decrement();
+    }
+    let half = time.half();
+    while low <= half && !T::wins(time, dist, &low) {
+        low = low.increment();
+    }
+
+    // nothing wins before the peak at `time / 2`, so nothing wins at all
+    if low > half {
+        return None;
+    }
+
+    // distance is symmetric around `time / 2`
+    let high = time.minus(&low);
+    Some(low..=high)
+}
+
+impl Race {
+    pub fn new(time: u64, dist: u64) -> Race {
+        Race { time, dist }
+    }
+
+    pub fn distance(&self, hold: u64) -> u128 {
+        self.time
+            .checked_sub(hold)
+            .map_or(0, |rest| hold as u128 * rest as u128)
+    }
+
+    pub fn wins(&self, hold: u64) -> bool {
+        self.distance(hold) > self.dist as u128
+    }
+
+    /// Inclusive range of hold times that beat the record, if any
+    pub fn winning_range(&self) -> Option<RangeInclusive<u64>> {
+        winning_range(&self.time, &self.dist)
+    }
+
+    pub fn winning_options(&self) -> u64 {
+        self.winning_range()
+            .map_or(0, |range| range.end() - range.start() + 1)
+    }
+}
+
+/// Arbitrary precision version of [`Race`] for races beyond `u64`
+#[cfg(feature = "bigint")]
+pub mod big {
+    use super::{winning_range, RaceInt};
+    use num::{BigUint, One, Zero};
+    use std::ops::RangeInclusive;
+
+    impl RaceInt for BigUint {
+        fn wins(time: &BigUint, dist: &BigUint, hold: &BigUint) -> bool {
+            hold <= time && hold * (time - hold) > *dist
+        }
+
+        fn lower_root(time: &BigUint, dist: &BigUint) -> Option<BigUint> {
+            let square = time * time;
+            let four_dist = dist * 4u32;
+            if square < four_dist {
+                return None;
+            }
+            Some((time - (square - four_dist).sqrt()) / 2u32)
+        }
+
+        fn is_zero(&self) -> bool {
+            Zero::is_zero(self)
+        }
+
+        fn increment(&self) -> BigUint {
+            self + 1u32
+        }
+
+        fn decrement(&self) -> BigUint {
+            self - 1u32
+        }
+
+        fn half(&self) -> BigUint {
+            self / 2u32
+        }
+
+        fn minus(&self, other: &BigUint) -> BigUint {
+            self - other
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct BigRace {
+        pub time: BigUint,
+        pub dist: BigUint,
+    }
+
+    impl BigRace {
+        pub fn new(time: BigUint, dist: BigUint) -> BigRace {
+            BigRace { time, dist }
+        }
+
+        pub fn wins(&self, hold: &BigUint) -> bool {
+            BigUint::wins(&self.time, &self.dist, hold)
+        }
+
+        /// Inclusive range of hold times that beat the record, if any
+        pub fn winning_range(&self) -> Option<RangeInclusive<BigUint>> {
+            winning_range(&self.time, &self.dist)
+        }
+
+        pub fn winning_options(&self) -> BigUint {
+            self.winning_range().map_or(BigUint::zero(), |range| {
+                range.end() - range.start() + BigUint::one()
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_races() {
+        assert_eq!(Some(2..=5), Race::new(7, 9).winning_range());
+        assert_eq!(Some(4..=11), Race::new(15, 40).winning_range());
+        // 10 and 20 only tie the record
+        assert_eq!(Some(11..=19), Race::new(30, 200).winning_range());
+        assert_eq!(71503, Race::new(71530, 940200).winning_options());
+    }
+
+    #[test]
+    fn unwinnable_races() {
+        assert_eq!(None, Race::new(0, 0).winning_range());
+        // best hold of 5 only ties the record
+        assert_eq!(None, Race::new(10, 25).winning_range());
+        assert_eq!(None, Race::new(10, 100).winning_range());
+        assert_eq!(None, Race::new(1, 0).winning_range());
+        assert_eq!(Some(1..=1), Race::new(2, 0).winning_range());
+    }
+
+    #[test]
+    fn large_race_boundaries_are_exact() {
+        let race = Race::new(u64::MAX, u64::MAX / 3 * 2);
+        let range = race.winning_range().expect("should have winning range");
+
+        assert!(race.wins(*range.start()));
+        assert!(!race.wins(range.start() - 1));
+        assert!(race.wins(*range.end()));
+        assert!(!race.wins(range.end() + 1));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_race_matches_u64_race() {
+        use super::big::BigRace;
+
+        for (time, dist) in [(7, 9), (30, 200), (10, 25), (71530, 940200)] {
+            let race = Race::new(time, dist);
+            let big = BigRace::new(time.into(), dist.into());
+
+            assert_eq!(
+                race.winning_options(),
+                big.winning_options().try_into().unwrap()
+            );
+        }
+    }
+}
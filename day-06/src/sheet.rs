@@ -0,0 +1,160 @@
+use crate::race::Race;
+use std::{error::Error, fmt};
+
+/// How the columns of a race sheet should be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kerning {
+    /// Each column is its own race
+    Separate,
+    /// All columns are one race with bad kerning, so digits are joined up
+    Single,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaceSheetError {
+    MissingRow(&'static str),
+    UnexpectedLine(String),
+    InvalidNumber { row: &'static str, value: String },
+    ColumnMismatch { times: usize, dists: usize },
+    NoRaces,
+}
+
+impl fmt::Display for RaceSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RaceSheetError::*;
+        match self {
+            MissingRow(label) => write!(f, "race sheet should have a `{label}:` row"),
+            UnexpectedLine(line) => write!(f, "unexpected line in race sheet: `{line}`"),
+            InvalidNumber { row, value } => write!(f, "invalid `{row}` value: `{value}`"),
+            ColumnMismatch { times, dists } => {
+                write!(f, "{times} time columns but {dists} distance columns")
+            }
+            NoRaces => write!(f, "race sheet has no races"),
+        }
+    }
+}
+
+impl Error for RaceSheetError {}
+
+///
+/// A parsed `Time:` / `Distance:` table
+///
+/// ```text
+/// Time:      7  15   30
+/// Distance:  9  40  200
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceSheet {
+    pub races: Vec<Race>,
+}
+
+impl RaceSheet {
+    pub fn parse(input: &str, kerning: Kerning) -> Result<RaceSheet, RaceSheetError> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        let times = row(lines.next(), "Time", kerning)?;
+        let dists = row(lines.next(), "Distance", kerning)?;
+
+        if let Some(line) = lines.next() {
+            return Err(RaceSheetError::UnexpectedLine(line.to_string()));
+        }
+
+        if times.len() != dists.len() {
+            return Err(RaceSheetError::ColumnMismatch {
+                times: times.len(),
+                dists: dists.len(),
+            });
+        }
+
+        if times.is_empty() {
+            return Err(RaceSheetError::NoRaces);
+        }
+
+        let races = times
+            .into_iter()
+            .zip(dists)
+            .map(|(time, dist)| Race::new(time, dist))
+            .collect();
+
+        Ok(RaceSheet { races })
+    }
+}
+
+/// Parses a `label: 1 2 3` row into its values
+fn row(
+    line: Option<&str>,
+    label: &'static str,
+    kerning: Kerning,
+) -> Result<Vec<u64>, RaceSheetError> {
+    let values = line
+        .and_then(|line| line.strip_prefix(label))
+        .and_then(|line| line.strip_prefix(':'))
+        .ok_or(RaceSheetError::MissingRow(label))?;
+
+    let columns: Vec<&str> = values.split_whitespace().collect();
+    let columns = match kerning {
+        Kerning::Separate => columns.iter().map(|column| column.to_string()).collect(),
+        Kerning::Single if columns.is_empty() => vec![],
+        Kerning::Single => vec![columns.concat()],
+    };
+
+    columns
+        .into_iter()
+        .map(|value| {
+            value
+                .parse::<u64>()
+                .map_err(|_| RaceSheetError::InvalidNumber { row: label, value })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn parses_both_kerning_modes() {
+        let separate = RaceSheet::parse(EXAMPLE, Kerning::Separate).expect("should parse");
+        let single = RaceSheet::parse(EXAMPLE, Kerning::Single).expect("should parse");
+
+        assert_eq!(
+            vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)],
+            separate.races
+        );
+        assert_eq!(vec![Race::new(71530, 940200)], single.races);
+    }
+
+    #[test]
+    fn reports_malformed_sheets() {
+        use RaceSheetError::*;
+
+        let cases = [
+            (
+                "Time: 7 15\nDistance: 9",
+                ColumnMismatch { times: 2, dists: 1 },
+            ),
+            ("Time: 7 15", MissingRow("Distance")),
+            ("Distance: 9\nTime: 7", MissingRow("Time")),
+            (
+                "Time: 7 x\nDistance: 9 40",
+                InvalidNumber {
+                    row: "Time",
+                    value: "x".to_string(),
+                },
+            ),
+            ("Time:\nDistance:", NoRaces),
+            (
+                "Time: 7\nDistance: 9\nSpeed: 1",
+                UnexpectedLine("Speed: 1".to_string()),
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(Err(expected), RaceSheet::parse(input, Kerning::Separate));
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use day_07::*;
 
+/// Parts 1 and 2 as they were before the `CamelRules` engine, to measure it against
+mod baseline;
+
 pub fn part1(c: &mut Criterion) {
     let input = black_box(include_str!("../input/input1.txt"));
     c.bench_function("day_07::part1", |b| {
@@ -15,5 +18,44 @@ pub fn part2(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, part1, part2);
+pub fn part1_baseline(c: &mut Criterion) {
+    let input = black_box(include_str!("../input/input1.txt"));
+    c.bench_function("day_07::part1_baseline", |b| {
+        b.iter(|| baseline::part1::run(input).expect("should benchmark part1 baseline"))
+    });
+}
+
+pub fn part2_baseline(c: &mut Criterion) {
+    let input = black_box(include_str!("../input/input1.txt"));
+    c.bench_function("day_07::part2_baseline", |b| {
+        b.iter(|| baseline::part2::run(input).expect("should benchmark part2 baseline"))
+    });
+}
+
+pub fn ranking(c: &mut Criterion) {
+    let input = black_box(include_str!("../input/input1.txt"));
+    let rules = camel::CamelRules::jokers();
+    let mut group = c.benchmark_group("day_07::ranking");
+
+    group.bench_function("packed", |b| {
+        b.iter(|| camel::rank_hands(input, &rules).expect("should benchmark packed"))
+    });
+
+    group.bench_function("comparison", |b| {
+        b.iter(|| {
+            camel::rank_hands_by_comparison(input, &rules).expect("should benchmark comparison")
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    part1,
+    part2,
+    part1_baseline,
+    part2_baseline,
+    ranking
+);
 criterion_main!(benches);
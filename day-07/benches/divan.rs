@@ -1,5 +1,8 @@
 use day_07::*;
 
+/// Parts 1 and 2 as they were before the `CamelRules` engine, to measure it against
+mod baseline;
+
 fn main() {
     divan::main();
 }
@@ -15,3 +18,28 @@ fn part2() {
     let input = divan::black_box(include_str!("../input/input1.txt"));
     part2::run(input).expect("should benchmark part 2");
 }
+
+#[divan::bench]
+fn part1_baseline() {
+    let input = divan::black_box(include_str!("../input/input1.txt"));
+    baseline::part1::run(input).expect("should benchmark part 1 baseline");
+}
+
+#[divan::bench]
+fn part2_baseline() {
+    let input = divan::black_box(include_str!("../input/input1.txt"));
+    baseline::part2::run(input).expect("should benchmark part 2 baseline");
+}
+
+#[divan::bench]
+fn ranking_packed() {
+    let input = divan::black_box(include_str!("../input/input1.txt"));
+    camel::rank_hands(input, &camel::CamelRules::jokers()).expect("should benchmark packed");
+}
+
+#[divan::bench]
+fn ranking_comparison() {
+    let input = divan::black_box(include_str!("../input/input1.txt"));
+    camel::rank_hands_by_comparison(input, &camel::CamelRules::jokers())
+        .expect("should benchmark comparison");
+}
@@ -0,0 +1,27 @@
+use day_07::{camel::CamelRules, report::Report};
+use std::{env, error::Error, fs};
+
+///
+/// Prints the full ranking report
+///
+/// ```text
+/// cargo run --bin day-07-report -- [part1|part2] [input path]
+/// ```
+///
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+
+    let rules = match args.next().as_deref() {
+        None | Some("part2") => CamelRules::jokers(),
+        Some("part1") => CamelRules::standard(),
+        Some(other) => return Err(format!("unknown part `{other}`").into()),
+    };
+
+    let input = match args.next() {
+        Some(path) => fs::read_to_string(path)?,
+        None => include_str!("../../input/input1.txt").to_string(),
+    };
+
+    println!("{}", Report::build(&input, &rules)?);
+    Ok(())
+}
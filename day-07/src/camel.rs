@@ -0,0 +1,358 @@
+use crate::wild::best_substitution;
+use std::{cmp::Ordering, error::Error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CamelError {
+    InvalidCard(char),
+    WrongHandSize { expected: usize, found: usize },
+    MissingBid(String),
+    InvalidBid(String),
+}
+
+impl fmt::Display for CamelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CamelError::*;
+        match self {
+            InvalidCard(card) => write!(f, "invalid card `{card}`"),
+            WrongHandSize { expected, found } => {
+                write!(f, "hand should have {expected} cards, found {found}")
+            }
+            MissingBid(line) => write!(f, "hand has no bid: `{line}`"),
+            InvalidBid(bid) => write!(f, "invalid bid `{bid}`"),
+        }
+    }
+}
+
+impl Error for CamelError {}
+
+/// Order in which card positions are compared when two hands share an identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TieBreak {
+    LeftToRight,
+    RightToLeft,
+}
+
+///
+/// Rules for a game of Camel Cards
+///
+/// - `card_order`: every valid card, weakest to strongest
+/// - `wild_cards`: cards that count as whatever makes the hand strongest
+/// - `hand_size`: number of cards in each hand
+/// - `tie_break`: order card positions are compared in
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CamelRules {
+    pub card_order: Vec<char>,
+    pub wild_cards: Vec<char>,
+    pub hand_size: usize,
+    pub tie_break: TieBreak,
+}
+
+impl CamelRules {
+    /// Part 1 rules
+    pub fn standard() -> CamelRules {
+        CamelRules {
+            card_order: "23456789TJQKA".chars().collect(),
+            wild_cards: vec![],
+            hand_size: 5,
+            tie_break: TieBreak::LeftToRight,
+        }
+    }
+
+    /// Part 2 rules, `J` is now a joker and the weakest card
+    pub fn jokers() -> CamelRules {
+        CamelRules {
+            card_order: "J23456789TQKA".chars().collect(),
+            wild_cards: vec!['J'],
+            ..CamelRules::standard()
+        }
+    }
+
+    pub fn card_value(&self, name: char) -> Option<u32> {
+        self.card_order
+            .iter()
+            .position(|&card| card == name)
+            .map(|i| i as u32 + 1)
+    }
+
+    pub fn is_wild(&self, name: char) -> bool {
+        self.wild_cards.contains(&name)
+    }
+
+    /// Whether an identity nibble plus one nibble per card fits in a `u64`
+    pub fn packs_into_u64(&self) -> bool {
+        self.card_order.len() < 16 && self.hand_size < 16
+    }
+
+    /// Card positions in the order they should be compared
+    pub fn tie_break_positions(&self) -> Vec<usize> {
+        match self.tie_break {
+            TieBreak::LeftToRight => (0..self.hand_size).collect(),
+            TieBreak::RightToLeft => (0..self.hand_size).rev().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandIdentity {
+    FiveOfAKind = 7,
+    FourOfAKind = 6,
+    FullHouse = 5,
+    ThreeOfAKind = 4,
+    TwoPair = 3,
+    OnePair = 2,
+    HighCard = 1,
+}
+
+impl HandIdentity {
+    /// Classifies a hand from its group sizes, largest first
+    pub fn from_counts(counts: &[usize]) -> HandIdentity {
+        use HandIdentity::*;
+        let first = counts.first().copied().unwrap_or(0);
+        let second = counts.get(1).copied().unwrap_or(0);
+
+        match (first, second) {
+            (5.., _) => FiveOfAKind,
+            (4, _) => FourOfAKind,
+            (3, 2..) => FullHouse,
+            (3, _) => ThreeOfAKind,
+            (2, 2) => TwoPair,
+            (2, _) => OnePair,
+            _ => HighCard,
+        }
+    }
+
+    pub fn score(&self) -> u8 {
+        *self as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card {
+    pub value: u32,
+    pub name: char,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: Vec<Card>,
+    pub bid: u32,
+    pub identity: HandIdentity,
+    /// `cards` after wild cards were substituted
+    pub effective: Vec<Card>,
+}
+
+impl Hand {
+    pub fn parse(line: &str, rules: &CamelRules) -> Result<Hand, CamelError> {
+        let (cards, bid) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| CamelError::MissingBid(line.to_string()))?;
+
+        let bid = bid
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| CamelError::InvalidBid(bid.to_string()))?;
+
+        let cards: Vec<Card> = cards
+            .chars()
+            .map(|name| {
+                let value = rules
+                    .card_value(name)
+                    .ok_or(CamelError::InvalidCard(name))?;
+                Ok(Card { value, name })
+            })
+            .collect::<Result<_, _>>()?;
+
+        if cards.len() != rules.hand_size {
+            return Err(CamelError::WrongHandSize {
+                expected: rules.hand_size,
+                found: cards.len(),
+            });
+        }
+
+        let substitution = best_substitution(&cards, rules);
+
+        Ok(Hand {
+            cards,
+            bid,
+            identity: substitution.identity,
+            effective: substitution.cards,
+        })
+    }
+
+    /// Best identity the cards can reach once wild cards are substituted
+    pub fn identity(cards: &[Card], rules: &CamelRules) -> HandIdentity {
+        best_substitution(cards, rules).identity
+    }
+
+    ///
+    /// Packs the hand into one integer that sorts the same as [`Hand::compare`]
+    ///
+    /// ```text
+    /// KTJJT under joker rules, FourOfAKind = 6, K = 12, T = 10, J = 1
+    /// 0x6_CA11A
+    /// ```
+    ///
+    /// The identity is the top nibble, followed by one nibble per card in
+    /// tie-break order. Returns `None` if the rules don't fit in a `u64`.
+    ///
+    pub fn sort_key(&self, rules: &CamelRules) -> Option<u64> {
+        if !rules.packs_into_u64() {
+            return None;
+        }
+
+        let key = rules
+            .tie_break_positions()
+            .iter()
+            .fold(self.identity.score() as u64, |key, &i| {
+                key << 4 | self.cards[i].value as u64
+            });
+
+        Some(key)
+    }
+
+    pub fn compare(&self, other: &Hand, rules: &CamelRules) -> Ordering {
+        self.identity
+            .score()
+            .cmp(&other.identity.score())
+            .then_with(|| {
+                // compare card by card until no longer equal
+                rules
+                    .tie_break_positions()
+                    .iter()
+                    .map(|&i| self.cards[i].value.cmp(&other.cards[i].value))
+                    .find(|comp| comp.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+/// Parses every hand in input order
+pub fn parse_hands(input: &str, rules: &CamelRules) -> Result<Vec<Hand>, CamelError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Hand::parse(line, rules))
+        .collect()
+}
+
+/// Parses every hand and sorts them weakest to strongest, so index + 1 is the rank
+///
+/// Sorts on packed integer keys when the rules allow, see [`Hand::sort_key`].
+pub fn rank_hands(input: &str, rules: &CamelRules) -> Result<Vec<Hand>, CamelError> {
+    if !rules.packs_into_u64() {
+        return rank_hands_by_comparison(input, rules);
+    }
+
+    let hands = parse_hands(input, rules)?;
+    let mut keyed: Vec<(u64, Hand)> = hands
+        .into_iter()
+        .map(|hand| (hand.sort_key(rules).expect("rules should pack"), hand))
+        .collect();
+
+    keyed.sort_by_key(|(key, _)| *key);
+
+    Ok(keyed.into_iter().map(|(_, hand)| hand).collect())
+}
+
+/// Same as [`rank_hands`] but compares hands card by card
+pub fn rank_hands_by_comparison(input: &str, rules: &CamelRules) -> Result<Vec<Hand>, CamelError> {
+    let mut hands = parse_hands(input, rules)?;
+
+    hands.sort_by(|a, b| a.compare(b, rules));
+
+    Ok(hands)
+}
+
+/// Total of every hand's `bid * rank`
+pub fn total_winnings(input: &str, rules: &CamelRules) -> Result<u64, CamelError> {
+    let hands = rank_hands(input, rules)?;
+
+    Ok(hands
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, hand)| acc + hand.bid as u64 * (i as u64 + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_hands() {
+        use HandIdentity::*;
+
+        let rules = CamelRules::jokers();
+        let cases = [
+            ("32T3K 1", OnePair),
+            ("T55J5 1", FourOfAKind),
+            ("KTJJT 1", FourOfAKind),
+            ("JJJJJ 1", FiveOfAKind),
+            ("2345J 1", OnePair),
+            ("2233J 1", FullHouse),
+        ];
+
+        for (line, expected) in cases {
+            let hand = Hand::parse(line, &rules).expect("should parse hand");
+            assert_eq!(expected, hand.identity, "{line}");
+        }
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = CamelRules {
+            card_order: "123456".chars().collect(),
+            wild_cards: vec!['1', '6'],
+            hand_size: 3,
+            tie_break: TieBreak::RightToLeft,
+        };
+        let input = "\
+234 1
+612 10
+354 100
+253 1000";
+
+        let hands = rank_hands(input, &rules).expect("should rank hands");
+        let bids: Vec<u32> = hands.iter().map(|hand| hand.bid).collect();
+
+        // 253 < 234 < 354 comparing from the right, then 612 is three of a kind
+        assert_eq!(vec![1000, 1, 100, 10], bids);
+    }
+
+    #[test]
+    fn packed_keys_sort_like_comparison() {
+        let input = include_str!("../input/input1.txt");
+
+        for rules in [CamelRules::standard(), CamelRules::jokers()] {
+            let packed = rank_hands(input, &rules).expect("should rank hands");
+            let compared = rank_hands_by_comparison(input, &rules).expect("should rank hands");
+
+            assert_eq!(compared, packed);
+        }
+
+        let hand = Hand::parse("KTJJT 220", &CamelRules::jokers()).expect("should parse hand");
+        assert_eq!(Some(0x6_CA11A), hand.sort_key(&CamelRules::jokers()));
+    }
+
+    #[test]
+    fn reports_invalid_hands() {
+        let rules = CamelRules::standard();
+
+        assert_eq!(
+            Err(CamelError::InvalidCard('X')),
+            Hand::parse("2345X 1", &rules)
+        );
+        assert_eq!(
+            Err(CamelError::WrongHandSize {
+                expected: 5,
+                found: 4
+            }),
+            Hand::parse("2345 1", &rules)
+        );
+        assert_eq!(
+            Err(CamelError::MissingBid("23456".to_string())),
+            Hand::parse("23456", &rules)
+        );
+    }
+}
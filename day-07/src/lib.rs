@@ -1,2 +1,5 @@
+pub mod camel;
 pub mod part1;
 pub mod part2;
+pub mod report;
+pub mod wild;
@@ -0,0 +1,149 @@
+use crate::camel::{rank_hands, CamelError, CamelRules, Card, Hand, HandIdentity};
+use std::fmt;
+
+/// Every hand identity, strongest to weakest
+pub const IDENTITIES: [HandIdentity; 7] = [
+    HandIdentity::FiveOfAKind,
+    HandIdentity::FourOfAKind,
+    HandIdentity::FullHouse,
+    HandIdentity::ThreeOfAKind,
+    HandIdentity::TwoPair,
+    HandIdentity::OnePair,
+    HandIdentity::HighCard,
+];
+
+/// One hand's line in a [`Report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedHand {
+    pub rank: usize,
+    pub hand: String,
+    pub identity: HandIdentity,
+    /// Hand after wild card substitution
+    pub effective: String,
+    pub bid: u32,
+    /// `bid * rank`
+    pub winnings: u64,
+}
+
+impl RankedHand {
+    pub fn from(rank: usize, hand: &Hand) -> RankedHand {
+        let names = |cards: &[Card]| cards.iter().map(|card| card.name).collect();
+
+        RankedHand {
+            rank,
+            hand: names(&hand.cards),
+            identity: hand.identity,
+            effective: names(&hand.effective),
+            bid: hand.bid,
+            winnings: hand.bid as u64 * rank as u64,
+        }
+    }
+}
+
+///
+/// Full breakdown of a game, for auditing why each hand ranked where it did
+///
+/// - `hands`: every hand, weakest to strongest
+/// - `histogram`: number of hands of each identity, strongest to weakest
+/// - `total`: total winnings
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub hands: Vec<RankedHand>,
+    pub histogram: Vec<(HandIdentity, usize)>,
+    pub total: u64,
+}
+
+impl Report {
+    pub fn build(input: &str, rules: &CamelRules) -> Result<Report, CamelError> {
+        let hands: Vec<RankedHand> = rank_hands(input, rules)?
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| RankedHand::from(i + 1, hand))
+            .collect();
+
+        let histogram = IDENTITIES
+            .iter()
+            .map(|&identity| {
+                let count = hands
+                    .iter()
+                    .filter(|hand| hand.identity == identity)
+                    .count();
+                (identity, count)
+            })
+            .collect();
+
+        let total = hands.iter().map(|hand| hand.winnings).sum();
+
+        Ok(Report {
+            hands,
+            histogram,
+            total,
+        })
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rank_width = self.hands.len().to_string().len().max(4);
+
+        writeln!(
+            f,
+            "{:>rank_width$}  {:<8}  {:<12}  {:<8}  {:>6}  {:>10}",
+            "rank", "hand", "identity", "as", "bid", "winnings"
+        )?;
+        for hand in &self.hands {
+            writeln!(
+                f,
+                "{:>rank_width$}  {:<8}  {:<12}  {:<8}  {:>6}  {:>10}",
+                hand.rank,
+                hand.hand,
+                format!("{:?}", hand.identity),
+                hand.effective,
+                hand.bid,
+                hand.winnings
+            )?;
+        }
+
+        writeln!(f)?;
+        for (identity, count) in &self.histogram {
+            writeln!(f, "{:<12}  {count:>6}", format!("{identity:?}"))?;
+        }
+
+        writeln!(f)?;
+        write!(f, "total winnings: {}", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_report() {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+        let report = Report::build(input, &CamelRules::jokers()).expect("should build report");
+
+        let strongest = report.hands.last().expect("should have hands");
+        assert_eq!(
+            &RankedHand {
+                rank: 5,
+                hand: "KTJJT".to_string(),
+                identity: HandIdentity::FourOfAKind,
+                effective: "KTTTT".to_string(),
+                bid: 220,
+                winnings: 1100,
+            },
+            strongest
+        );
+
+        let counts: Vec<usize> = report.histogram.iter().map(|(_, count)| *count).collect();
+        assert_eq!(vec![0, 3, 0, 0, 1, 1, 0], counts);
+        assert_eq!(5905, report.total);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::camel::{CamelRules, Card, HandIdentity};
+
+/// What a hand's wild cards were replaced with, and the identity that gives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    pub cards: Vec<Card>,
+    pub identity: HandIdentity,
+}
+
+impl Substitution {
+    /// The substituted hand as card names, e.g. `KTTTT` for `KTJJT`
+    pub fn hand(&self) -> String {
+        self.cards.iter().map(|card| card.name).collect()
+    }
+}
+
+///
+/// Picks the strongest concrete hand the wild cards can become
+///
+/// Hand identities rank by the largest group first and the second largest
+/// group after that, so every wild card joins the largest non-wild group.
+/// Ties go to the strongest rank. A hand of only wild cards becomes the
+/// strongest non-wild card. Works for any number of wild ranks and any
+/// hand size.
+///
+/// Only the identity changes, tie-breaks still use the original cards.
+///
+pub fn best_substitution(cards: &[Card], rules: &CamelRules) -> Substitution {
+    let mut counts: Vec<usize> = vec![0; rules.card_order.len() + 1];
+    cards
+        .iter()
+        .filter(|card| !rules.is_wild(card.name))
+        .for_each(|card| counts[card.value as usize] += 1);
+
+    let target = match counts.iter().max() {
+        Some(&max) if max > 0 => {
+            let value = counts.iter().rposition(|&count| count == max).unwrap();
+            Some(Card {
+                value: value as u32,
+                name: rules.card_order[value - 1],
+            })
+        }
+        // nothing to copy, so become the strongest card that isn't wild
+        _ => rules
+            .card_order
+            .iter()
+            .rev()
+            .find(|&&name| !rules.is_wild(name))
+            .map(|&name| Card {
+                value: rules.card_value(name).unwrap(),
+                name,
+            }),
+    };
+
+    let cards: Vec<Card> = cards
+        .iter()
+        .map(|&card| match target {
+            Some(target) if rules.is_wild(card.name) => target,
+            _ => card,
+        })
+        .collect();
+
+    counts.fill(0);
+    cards
+        .iter()
+        .filter(|card| !rules.is_wild(card.name))
+        .for_each(|card| counts[card.value as usize] += 1);
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+
+    // only reachable when every card is wild, count them as one group
+    if counts[0] == 0 {
+        counts[0] = cards.len();
+    }
+
+    Substitution {
+        identity: HandIdentity::from_counts(&counts),
+        cards,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camel::TieBreak;
+
+    fn cards(hand: &str, rules: &CamelRules) -> Vec<Card> {
+        hand.chars()
+            .map(|name| Card {
+                value: rules.card_value(name).unwrap(),
+                name,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_substituted_hands() {
+        let rules = CamelRules::jokers();
+        let cases = [
+            ("KTJJT", "KTTTT", HandIdentity::FourOfAKind),
+            ("QQQJA", "QQQQA", HandIdentity::FourOfAKind),
+            ("2345J", "23455", HandIdentity::OnePair),
+            ("JJJJJ", "AAAAA", HandIdentity::FiveOfAKind),
+            ("32T3K", "32T3K", HandIdentity::OnePair),
+        ];
+
+        for (hand, expected, identity) in cases {
+            let substitution = best_substitution(&cards(hand, &rules), &rules);
+            assert_eq!(expected, substitution.hand());
+            assert_eq!(identity, substitution.identity);
+        }
+    }
+
+    #[test]
+    fn matches_exhaustive_search() {
+        let rules = CamelRules {
+            card_order: "W123V".chars().collect(),
+            wild_cards: vec!['W', 'V'],
+            hand_size: 5,
+            tie_break: TieBreak::LeftToRight,
+        };
+        let concrete = ['1', '2', '3'];
+
+        // every hand of every size up to five cards
+        let mut hands: Vec<String> = vec![String::new()];
+        for _ in 0..rules.hand_size {
+            hands = hands
+                .iter()
+                .flat_map(|hand| {
+                    rules.card_order.iter().map(move |&card| {
+                        let mut hand = hand.clone();
+                        hand.push(card);
+                        hand
+                    })
+                })
+                .collect();
+
+            for hand in &hands {
+                // try every possible replacement for every wild card
+                let mut options: Vec<String> = vec![String::new()];
+                for card in hand.chars() {
+                    let choices: Vec<char> = if rules.is_wild(card) {
+                        concrete.to_vec()
+                    } else {
+                        vec![card]
+                    };
+                    options = options
+                        .iter()
+                        .flat_map(|option| {
+                            choices
+                                .iter()
+                                .map(move |&choice| format!("{option}{choice}"))
+                        })
+                        .collect();
+                }
+
+                let best = options
+                    .iter()
+                    .map(|option| best_substitution(&cards(option, &rules), &rules).identity)
+                    .max_by_key(|identity| identity.score())
+                    .unwrap();
+                let substitution = best_substitution(&cards(hand, &rules), &rules);
+
+                assert_eq!(best, substitution.identity, "{hand}");
+                assert_eq!(
+                    substitution.identity,
+                    best_substitution(&substitution.cards, &rules).identity,
+                    "{hand}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use day_08::{
+    dot::{to_dot, DotOptions},
+    network::Network,
+};
+use std::{env, error::Error, fs};
+
+///
+/// Prints the network as a Graphviz DOT graph
+///
+/// ```text
+/// cargo run --bin day-08-dot -- [--reachable] [--path LRL...] [input path] > network.dot
+/// ```
+///
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut options = DotOptions::default();
+    let mut path_arg = None;
+    let mut input_path = None;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--reachable" => options.reachable_only = true,
+            "--path" => path_arg = Some(args.next().ok_or("`--path` needs instructions")?),
+            _ if input_path.is_none() => input_path = Some(arg),
+            _ => return Err(format!("unexpected argument `{arg}`").into()),
+        }
+    }
+    options.path = path_arg.map(String::as_str);
+
+    let input = match input_path {
+        Some(path) => fs::read_to_string(path)?,
+        None => include_str!("../../input/input1.txt").to_string(),
+    };
+
+    let network = Network::parse(&input)?;
+    println!("{}", to_dot(&network, &options)?);
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use crate::network::Network;
+use num::Integer;
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CycleError {
+    NoGhosts,
+    NeverAlign,
+    Overflow,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CycleError::*;
+        match self {
+            NoGhosts => write!(f, "there are no ghosts to walk"),
+            NeverAlign => write!(f, "the ghosts never reach end nodes at the same time"),
+            Overflow => write!(
+                f,
+                "the ghosts' cycles are too long to line up without overflowing"
+            ),
+        }
+    }
+}
+
+impl Error for CycleError {}
+
+///
+/// Shape of one ghost's walk through `(node, instruction index)` states
+///
+/// Every walk eventually repeats a state, after which it loops forever.
+///
+/// - `tail`: steps taken before entering the loop
+/// - `length`: steps in one trip around the loop
+/// - `tail_ends`: steps before the loop that land on an end node
+/// - `cycle_ends`: steps in `tail..tail + length` that land on an end node,
+///   each repeating every `length` steps
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub start: u32,
+    pub tail: u64,
+    pub length: u64,
+    pub tail_ends: Vec<u64>,
+    pub cycle_ends: Vec<u64>,
+}
+
+impl GhostCycle {
+    /// Finds the walk's loop with Brent's algorithm, so no states are stored
+    pub fn analyse(network: &Network, start: u32, is_end: impl Fn(u32) -> bool) -> GhostCycle {
+        let len = network.instructions.len() as u64;
+        let next = |(node, i): (u32, u64)| (network.step(node, i), (i + 1) % len);
+        let origin = (start, 0);
+
+        // find the loop length by doubling the tortoise's search window
+        let mut power: u64 = 1;
+        let mut length: u64 = 1;
+        let mut tortoise = origin;
+        let mut hare = next(origin);
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare;
+                power *= 2;
+                length = 0;
+            }
+            hare = next(hare);
+            length += 1;
+        }
+
+        // a hare `length` steps ahead meets the tortoise where the loop starts
+        let mut tortoise = origin;
+        let mut hare = origin;
+        for _ in 0..length {
+            hare = next(hare);
+        }
+        let mut tail: u64 = 0;
+        while tortoise != hare {
+            tortoise = next(tortoise);
+            hare = next(hare);
+            tail += 1;
+        }
+
+        // walk the tail and one trip around the loop, recording end nodes
+        let mut tail_ends = vec![];
+        let mut cycle_ends = vec![];
+        let mut state = origin;
+        for step in 0..tail + length {
+            if is_end(state.0) {
+                if step < tail {
+                    tail_ends.push(step);
+                } else {
+                    cycle_ends.push(step);
+                }
+            }
+            state = next(state);
+        }
+
+        GhostCycle {
+            start,
+            tail,
+            length,
+            tail_ends,
+            cycle_ends,
+        }
+    }
+
+    /// Whether the ghost is on an end node after `step` steps
+    pub fn ends_at(&self, step: u64) -> bool {
+        if step < self.tail {
+            self.tail_ends.binary_search(&step).is_ok()
+        } else {
+            let offset = self.tail + (step - self.tail) % self.length;
+            self.cycle_ends.binary_search(&offset).is_ok()
+        }
+    }
+}
+
+/// Outcome of merging two congruences
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crt {
+    /// No step satisfies both
+    Incompatible,
+    /// The merged congruence doesn't fit in `i128`
+    Overflow,
+    /// Every step `x = residue (mod modulus)`
+    Solution(i128, i128),
+}
+
+/// Solves `x = a1 (mod n1)` and `x = a2 (mod n2)` for moduli that need not be coprime
+pub fn crt(a1: i128, n1: i128, a2: i128, n2: i128) -> Crt {
+    let gcd = n1.extended_gcd(&n2);
+    let diff = a2 - a1;
+    if diff % gcd.gcd != 0 {
+        return Crt::Incompatible;
+    }
+
+    let merged = || {
+        let modulus = (n1 / gcd.gcd).checked_mul(n2)?;
+        let k = (diff / gcd.gcd % (n2 / gcd.gcd)).checked_mul(gcd.x)? % (n2 / gcd.gcd);
+        let x = a1.checked_add(n1.checked_mul(k)?)?.rem_euclid(modulus);
+        Some((x, modulus))
+    };
+
+    match merged() {
+        Some((x, modulus)) => Crt::Solution(x, modulus),
+        None => Crt::Overflow,
+    }
+}
+
+///
+/// First step (at least one) where every ghost is on an end node
+///
+/// Steps before every ghost is looping are checked directly against each
+/// ghost's tail. After that each ghost contributes `step = end (mod length)`
+/// for each of its loop's end offsets, and every combination is merged with
+/// [`crt`].
+///
+pub fn align(ghosts: &[GhostCycle]) -> Result<u64, CycleError> {
+    if ghosts.is_empty() {
+        return Err(CycleError::NoGhosts);
+    }
+
+    let max_tail = ghosts.iter().map(|ghost| ghost.tail).max().unwrap();
+
+    // before `max_tail` some ghost is still in its tail, so only its tail ends can match
+    let early = ghosts
+        .iter()
+        .flat_map(|ghost| &ghost.tail_ends)
+        .filter(|&&step| step >= 1 && step < max_tail)
+        .filter(|&&step| ghosts.iter().all(|ghost| ghost.ends_at(step)))
+        .min();
+    if let Some(&step) = early {
+        return Ok(step);
+    }
+
+    // every ghost is looping, merge each ghost's `(residue, modulus)` options
+    let mut options: Vec<(i128, i128)> = vec![(0, 1)];
+    for ghost in ghosts {
+        let length = ghost.length as i128;
+        let mut merged: Vec<(i128, i128)> = vec![];
+        for &(a, n) in &options {
+            for &end in &ghost.cycle_ends {
+                match crt(a, n, end as i128 % length, length) {
+                    Crt::Incompatible => {}
+                    // a lost option might have been the answer, so don't call it `NeverAlign`
+                    Crt::Overflow => return Err(CycleError::Overflow),
+                    Crt::Solution(residue, modulus) => merged.push((residue, modulus)),
+                }
+            }
+        }
+        merged.sort_unstable();
+        merged.dedup();
+
+        if merged.is_empty() {
+            return Err(CycleError::NeverAlign);
+        }
+        options = merged;
+    }
+
+    // smallest step at or after `max_tail` for each option
+    let floor = max_tail.max(1) as i128;
+    options
+        .iter()
+        .map(|&(residue, modulus)| floor + (residue - floor).rem_euclid(modulus))
+        .min()
+        .and_then(|step| u64::try_from(step).ok())
+        .ok_or(CycleError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NodeKind;
+
+    fn ghosts(input: &str) -> Vec<GhostCycle> {
+        let network = Network::parse(input).expect("should parse network");
+        network
+            .nodes_of_kind(NodeKind::Start)
+            .map(|start| {
+                GhostCycle::analyse(&network, start, |node| network.kind(node) == NodeKind::End)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_tails_and_cycles() {
+        let input = "\
+L
+
+1A = (1B, 1A)
+1B = (1Z, 1B)
+1Z = (1C, 1Z)
+1C = (1B, 1C)";
+
+        let ghost = ghosts(input).pop().expect("should have a ghost");
+
+        assert_eq!(1, ghost.tail);
+        assert_eq!(3, ghost.length);
+        assert_eq!(Vec::<u64>::new(), ghost.tail_ends);
+        assert_eq!(vec![2], ghost.cycle_ends);
+        assert!(ghost.ends_at(8));
+        assert!(!ghost.ends_at(9));
+    }
+
+    #[test]
+    fn aligns_ghosts_with_offset_cycles() {
+        // first hits are 2 and 1, but the ghosts only line up at 5
+        let input = "\
+L
+
+1A = (1B, 1A)
+1B = (1Z, 1B)
+1Z = (1C, 1Z)
+1C = (1B, 1C)
+2A = (2Z, 2A)
+2Z = (2B, 2Z)
+2B = (2Z, 2B)";
+
+        assert_eq!(Ok(5), align(&ghosts(input)));
+    }
+
+    #[test]
+    fn reports_ghosts_that_never_align() {
+        let input = "\
+L
+
+2A = (2B, 2A)
+2B = (2Z, 2B)
+2Z = (2B, 2Z)
+3A = (3Z, 3A)
+3Z = (3B, 3Z)
+3B = (3Z, 3B)";
+
+        assert_eq!(Err(CycleError::NeverAlign), align(&ghosts(input)));
+    }
+
+    #[test]
+    fn generalised_crt() {
+        assert_eq!(Crt::Solution(5, 6), crt(2, 3, 1, 2));
+        assert_eq!(Crt::Solution(10, 12), crt(4, 6, 2, 4));
+        assert_eq!(Crt::Incompatible, crt(1, 4, 2, 6));
+        assert_eq!(Crt::Overflow, crt(1, 1 << 126, 1, 3));
+    }
+
+    #[test]
+    fn reports_overflow_for_long_cycles() {
+        // every ghost is on an end node one step in, and every `length` steps after
+        let ghost = |length: u64| GhostCycle {
+            start: 0,
+            tail: 0,
+            length,
+            tail_ends: vec![],
+            cycle_ends: vec![1],
+        };
+        let lengths = [
+            1_000_000_007,
+            1_000_000_009,
+            1_000_000_021,
+            1_000_000_033,
+            1_000_000_087,
+        ];
+
+        let two: Vec<GhostCycle> = lengths[..2].iter().map(|&length| ghost(length)).collect();
+        assert_eq!(Ok(1), align(&two));
+
+        // the merged modulus passes `i128::MAX` at the fifth ghost
+        let five: Vec<GhostCycle> = lengths.iter().map(|&length| ghost(length)).collect();
+        assert_eq!(Err(CycleError::Overflow), align(&five));
+    }
+}
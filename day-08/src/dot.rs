@@ -0,0 +1,221 @@
+use crate::network::{Instruction, Network, NetworkError, NodeKind};
+use std::collections::{BTreeSet, HashSet};
+
+/// Options for [`to_dot`]
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions<'a> {
+    /// Instructions to follow once from every start node, highlighting the edges taken
+    pub path: Option<&'a str>,
+    /// Collapse nodes no start node can reach into a single placeholder node
+    pub reachable_only: bool,
+}
+
+/// Nodes reachable from any start node, following either edge
+pub fn reachable(network: &Network) -> Vec<bool> {
+    let mut seen = vec![false; network.len()];
+    let mut stack: Vec<u32> = network.nodes_of_kind(NodeKind::Start).collect();
+
+    while let Some(node) = stack.pop() {
+        if seen[node as usize] {
+            continue;
+        }
+        seen[node as usize] = true;
+        stack.extend(network.edges[node as usize]);
+    }
+
+    seen
+}
+
+/// Quotes a node label as a DOT ID, escaping `"` and `\`
+fn quote(label: &str) -> String {
+    let mut id = String::with_capacity(label.len() + 2);
+    id.push('"');
+    for c in label.chars() {
+        if matches!(c, '"' | '\\') {
+            id.push('\\');
+        }
+        id.push(c);
+    }
+    id.push('"');
+    id
+}
+
+/// An ID for the unreachable placeholder that no node in the network uses
+fn placeholder(network: &Network) -> String {
+    let mut id = String::from("__unreachable__");
+    while network.index(&id).is_some() {
+        id.push('_');
+    }
+    quote(&id)
+}
+
+///
+/// Writes the network as a Graphviz DOT graph
+///
+/// - start nodes are green, end nodes are red
+/// - edges are labelled with the instruction(s) that follow them
+/// - edges walked by `options.path` are drawn thick and blue
+///
+/// Render with `dot -Tsvg network.dot -o network.svg`.
+///
+pub fn to_dot(network: &Network, options: &DotOptions) -> Result<String, NetworkError> {
+    let mut highlighted: HashSet<(u32, Instruction)> = HashSet::new();
+    if let Some(path) = options.path {
+        let path: Vec<Instruction> = path
+            .chars()
+            .map(Instruction::from)
+            .collect::<Result<_, _>>()?;
+
+        for mut node in network.nodes_of_kind(NodeKind::Start) {
+            for &dir in &path {
+                highlighted.insert((node, dir));
+                node = network.edges[node as usize][dir as usize];
+            }
+        }
+    }
+
+    let shown = if options.reachable_only {
+        reachable(network)
+    } else {
+        vec![true; network.len()]
+    };
+
+    let mut dot = String::from("digraph network {\n    node [shape=circle];\n");
+
+    for node in 0..network.len() as u32 {
+        if !shown[node as usize] {
+            continue;
+        }
+        let style = match network.kind(node) {
+            NodeKind::Start => " [style=filled, fillcolor=palegreen]",
+            NodeKind::End => " [style=filled, fillcolor=lightcoral]",
+            NodeKind::Regular => "",
+        };
+        dot.push_str(&format!("    {}{style};\n", quote(network.label(node))));
+    }
+
+    let placeholder = placeholder(network);
+    let hidden = shown.iter().filter(|&&shown| !shown).count();
+    if hidden > 0 {
+        dot.push_str(&format!(
+            "    {placeholder} [shape=box, style=dashed, label=\"{hidden} unreachable\"];\n"
+        ));
+    }
+
+    let mut collapsed: BTreeSet<u32> = BTreeSet::new();
+    for node in 0..network.len() as u32 {
+        let [left, right] = network.edges[node as usize];
+
+        if !shown[node as usize] {
+            // keep edges from the hidden nodes into the shown ones
+            collapsed.extend([left, right].iter().filter(|&&next| shown[next as usize]));
+            continue;
+        }
+
+        let is_highlighted = |dir| highlighted.contains(&(node, dir));
+        let edges = if left == right {
+            vec![(
+                left,
+                "LR",
+                is_highlighted(Instruction::Left) || is_highlighted(Instruction::Right),
+            )]
+        } else {
+            vec![
+                (left, "L", is_highlighted(Instruction::Left)),
+                (right, "R", is_highlighted(Instruction::Right)),
+            ]
+        };
+
+        for (next, label, is_highlighted) in edges {
+            let style = if is_highlighted {
+                ", color=blue, penwidth=3"
+            } else {
+                ""
+            };
+            dot.push_str(&format!(
+                "    {} -> {} [label=\"{label}\"{style}];\n",
+                quote(network.label(node)),
+                quote(network.label(next))
+            ));
+        }
+    }
+
+    for next in collapsed {
+        dot.push_str(&format!(
+            "    {placeholder} -> {} [style=dashed];\n",
+            quote(network.label(next))
+        ));
+    }
+
+    dot.push('}');
+
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_dot() {
+        let input = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+XXX = (XXX, BBB)";
+
+        let network = Network::parse(input).expect("should parse network");
+        let options = DotOptions {
+            path: Some("LR"),
+            reachable_only: true,
+        };
+        let dot = to_dot(&network, &options).expect("should export dot");
+
+        let expected = "\
+digraph network {
+    node [shape=circle];
+    \"AAA\" [style=filled, fillcolor=palegreen];
+    \"BBB\";
+    \"ZZZ\" [style=filled, fillcolor=lightcoral];
+    \"__unreachable__\" [shape=box, style=dashed, label=\"1 unreachable\"];
+    \"AAA\" -> \"BBB\" [label=\"LR\", color=blue, penwidth=3];
+    \"BBB\" -> \"AAA\" [label=\"L\"];
+    \"BBB\" -> \"ZZZ\" [label=\"R\", color=blue, penwidth=3];
+    \"ZZZ\" -> \"ZZZ\" [label=\"LR\"];
+    \"__unreachable__\" -> \"BBB\" [style=dashed];
+}";
+
+        assert_eq!(expected, dot);
+    }
+
+    #[test]
+    fn escapes_labels() {
+        let input = "\
+L
+
+A\"A = (B\\B, B\\B)
+B\\B = (B\\B, B\\B)
+__unreachable__ = (B\\B, B\\B)";
+
+        let network = Network::parse(input).expect("should parse network");
+        let options = DotOptions {
+            path: None,
+            reachable_only: true,
+        };
+        let dot = to_dot(&network, &options).expect("should export dot");
+
+        let expected = r#"digraph network {
+    node [shape=circle];
+    "A\"A" [style=filled, fillcolor=palegreen];
+    "B\\B";
+    "__unreachable___" [shape=box, style=dashed, label="1 unreachable"];
+    "A\"A" -> "B\\B" [label="LR"];
+    "B\\B" -> "B\\B" [label="LR"];
+    "__unreachable___" -> "B\\B" [style=dashed];
+}"#;
+
+        assert_eq!(expected, dot);
+    }
+}
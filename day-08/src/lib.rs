@@ -1,2 +1,6 @@
+pub mod cycles;
+pub mod dot;
+pub mod network;
 pub mod part1;
 pub mod part2;
+pub mod query;
@@ -0,0 +1,221 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+pub const START_SUFFIX: char = 'A';
+pub const END_SUFFIX: char = 'Z';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    MissingInstructions,
+    InvalidInstruction(char),
+    MalformedNode(String),
+    DuplicateNode(String),
+    UnknownNode(String),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use NetworkError::*;
+        match self {
+            MissingInstructions => write!(f, "network should start with an instructions line"),
+            InvalidInstruction(dir) => write!(f, "invalid instruction `{dir}`"),
+            MalformedNode(line) => write!(f, "invalid node: `{line}`"),
+            DuplicateNode(label) => write!(f, "node `{label}` is defined more than once"),
+            UnknownNode(label) => write!(f, "node `{label}` is never defined"),
+        }
+    }
+}
+
+impl Error for NetworkError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Start,
+    End,
+    Regular,
+}
+
+impl NodeKind {
+    pub fn from(label: &str) -> NodeKind {
+        match label.chars().last() {
+            Some(START_SUFFIX) => NodeKind::Start,
+            Some(END_SUFFIX) => NodeKind::End,
+            _ => NodeKind::Regular,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Left = 0,
+    Right = 1,
+}
+
+impl Instruction {
+    pub fn from(dir: char) -> Result<Instruction, NetworkError> {
+        match dir {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(NetworkError::InvalidInstruction(dir)),
+        }
+    }
+}
+
+///
+/// Haunted Wasteland network, compiled for traversal
+///
+/// - node labels are interned into dense `u32` indices in order of definition
+/// - each node's `[left, right]` neighbours are stored by index
+/// - stepping is plain indexing, so walks never allocate
+///
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub instructions: Vec<Instruction>,
+    pub labels: Vec<String>,
+    pub edges: Vec<[u32; 2]>,
+    pub kinds: Vec<NodeKind>,
+    indices: HashMap<String, u32>,
+}
+
+impl Network {
+    pub fn parse(input: &str) -> Result<Network, NetworkError> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        let instructions: Vec<Instruction> = lines
+            .next()
+            .ok_or(NetworkError::MissingInstructions)?
+            .chars()
+            .map(Instruction::from)
+            .collect::<Result<_, _>>()?;
+
+        if instructions.is_empty() {
+            return Err(NetworkError::MissingInstructions);
+        }
+
+        // `AAA = (BBB, CCC)`
+        let records: Vec<(&str, &str, &str)> = lines
+            .map(|line| {
+                let malformed = || NetworkError::MalformedNode(line.to_string());
+                let (label, rest) = line.split_once(" = ").ok_or_else(malformed)?;
+                let (left, right) = rest
+                    .strip_prefix('(')
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .and_then(|rest| rest.split_once(", "))
+                    .ok_or_else(malformed)?;
+
+                Ok((label, left, right))
+            })
+            .collect::<Result<_, NetworkError>>()?;
+
+        let mut indices: HashMap<String, u32> = HashMap::with_capacity(records.len());
+        for (i, (label, _, _)) in records.iter().enumerate() {
+            if indices.insert(label.to_string(), i as u32).is_some() {
+                return Err(NetworkError::DuplicateNode(label.to_string()));
+            }
+        }
+
+        let index = |label: &str| {
+            indices
+                .get(label)
+                .copied()
+                .ok_or_else(|| NetworkError::UnknownNode(label.to_string()))
+        };
+
+        let edges: Vec<[u32; 2]> = records
+            .iter()
+            .map(|(_, left, right)| Ok([index(left)?, index(right)?]))
+            .collect::<Result<_, NetworkError>>()?;
+
+        let labels: Vec<String> = records
+            .iter()
+            .map(|(label, _, _)| label.to_string())
+            .collect();
+        let kinds = labels.iter().map(|label| NodeKind::from(label)).collect();
+
+        Ok(Network {
+            instructions,
+            labels,
+            edges,
+            kinds,
+            indices,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn index(&self, label: &str) -> Option<u32> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, node: u32) -> &str {
+        &self.labels[node as usize]
+    }
+
+    pub fn kind(&self, node: u32) -> NodeKind {
+        self.kinds[node as usize]
+    }
+
+    /// Indices of every node of the given kind
+    pub fn nodes_of_kind(&self, kind: NodeKind) -> impl Iterator<Item = u32> + '_ {
+        (0..self.len() as u32).filter(move |&node| self.kind(node) == kind)
+    }
+
+    /// Follows the instruction at `step`, wrapping around the instruction list
+    pub fn step(&self, node: u32, step: u64) -> u32 {
+        let i = (step % self.instructions.len() as u64) as usize;
+        self.edges[node as usize][self.instructions[i] as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_labels() {
+        let input = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+        let network = Network::parse(input).expect("should parse network");
+
+        assert_eq!(vec![[1, 1], [0, 2], [2, 2]], network.edges);
+        assert_eq!(Some(2), network.index("ZZZ"));
+        assert_eq!("BBB", network.label(1));
+        assert_eq!(NodeKind::End, network.kind(2));
+        // third instruction is `R`, then it wraps back to `L`
+        assert_eq!(2, network.step(1, 2));
+        assert_eq!(0, network.step(1, 3));
+    }
+
+    #[test]
+    fn reports_malformed_networks() {
+        use NetworkError::*;
+
+        let cases = [
+            ("", MissingInstructions),
+            ("LXR\n\nAAA = (AAA, AAA)", InvalidInstruction('X')),
+            (
+                "LR\n\nAAA = AAA, AAA",
+                MalformedNode("AAA = AAA, AAA".to_string()),
+            ),
+            ("LR\n\nAAA = (AAA, BBB)", UnknownNode("BBB".to_string())),
+            (
+                "LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)",
+                DuplicateNode("AAA".to_string()),
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(expected, Network::parse(input).unwrap_err());
+        }
+    }
+}
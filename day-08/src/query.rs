@@ -0,0 +1,283 @@
+use crate::{cycles::GhostCycle, network::Network};
+use regex::Regex;
+use std::{collections::HashSet, error::Error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnknownNode(String),
+    Unreachable { from: String },
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use QueryError::*;
+        match self {
+            UnknownNode(label) => write!(f, "node `{label}` is not in the network"),
+            Unreachable { from } => write!(f, "no matching node is ever reached from `{from}`"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+/// Which node labels a query is looking for
+#[derive(Debug, Clone)]
+pub enum NodePredicate {
+    Label(String),
+    Suffix(String),
+    Regex(Regex),
+    Set(HashSet<String>),
+}
+
+impl NodePredicate {
+    pub fn label(label: &str) -> NodePredicate {
+        NodePredicate::Label(label.to_string())
+    }
+
+    pub fn suffix(suffix: char) -> NodePredicate {
+        NodePredicate::Suffix(suffix.to_string())
+    }
+
+    pub fn matches(&self, label: &str) -> bool {
+        match self {
+            NodePredicate::Label(target) => label == target,
+            NodePredicate::Suffix(suffix) => label.ends_with(suffix.as_str()),
+            NodePredicate::Regex(regex) => regex.is_match(label),
+            NodePredicate::Set(labels) => labels.contains(label),
+        }
+    }
+
+    /// Evaluates the predicate once per node, so walks only index
+    pub fn compile(&self, network: &Network) -> Vec<bool> {
+        network
+            .labels
+            .iter()
+            .map(|label| self.matches(label))
+            .collect()
+    }
+}
+
+impl Network {
+    /// Indices of every node matching `predicate`
+    pub fn nodes_matching(&self, predicate: &NodePredicate) -> Vec<u32> {
+        (0..self.len() as u32)
+            .filter(|&node| predicate.matches(self.label(node)))
+            .collect()
+    }
+
+    fn start(&self, from: &str) -> Result<u32, QueryError> {
+        self.index(from)
+            .ok_or_else(|| QueryError::UnknownNode(from.to_string()))
+    }
+
+    ///
+    /// Steps taken from `from` until standing on a node matching `target`
+    ///
+    /// Walks until the first match, so the cost is the answer rather than the
+    /// size of the walk's loop. Proving a target unreachable runs Brent's
+    /// cycle detection, as in [`GhostCycle::analyse`], checking every step the
+    /// hare takes: by the time the hare meets the tortoise it has passed
+    /// through the whole tail and loop.
+    ///
+    pub fn steps_until(&self, from: &str, target: &NodePredicate) -> Result<u64, QueryError> {
+        let is_target = target.compile(self);
+        let start = self.start(from)?;
+        if is_target[start as usize] {
+            return Ok(0);
+        }
+
+        let len = self.instructions.len() as u64;
+        let next = |(node, i): (u32, u64)| (self.step(node, i), (i + 1) % len);
+
+        let mut power: u64 = 1;
+        let mut length: u64 = 1;
+        let mut tortoise = (start, 0);
+        let mut hare = next(tortoise);
+        let mut steps: u64 = 1;
+        while tortoise != hare {
+            if is_target[hare.0 as usize] {
+                return Ok(steps);
+            }
+            if power == length {
+                tortoise = hare;
+                power *= 2;
+                length = 0;
+            }
+            hare = next(hare);
+            length += 1;
+            steps += 1;
+        }
+
+        Err(QueryError::Unreachable {
+            from: from.to_string(),
+        })
+    }
+
+    ///
+    /// Every node matching `target` that the walk from `from` lands on, with the
+    /// first step it lands there
+    ///
+    /// Only the walk's tail and one trip around its loop are needed, later
+    /// steps revisit the same states.
+    ///
+    pub fn reachable_ends(
+        &self,
+        from: &str,
+        target: &NodePredicate,
+    ) -> Result<Vec<(String, u64)>, QueryError> {
+        let is_target = target.compile(self);
+        let start = self.start(from)?;
+        let cycle = GhostCycle::analyse(self, start, |node| is_target[node as usize]);
+
+        let mut seen: HashSet<u32> = HashSet::new();
+        let mut ends = vec![];
+        let mut node = start;
+        for step in 0..cycle.tail + cycle.length {
+            if is_target[node as usize] && seen.insert(node) {
+                ends.push((self.label(node).to_string(), step));
+            }
+            node = self.step(node, step);
+        }
+
+        Ok(ends)
+    }
+
+    /// Whether the walk from `from` ever lands on `to`
+    pub fn reaches(&self, from: &str, to: &str) -> Result<bool, QueryError> {
+        self.start(to)?;
+
+        match self.steps_until(from, &NodePredicate::label(to)) {
+            Ok(_) => Ok(true),
+            Err(QueryError::Unreachable { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn steps_until_predicates() {
+        let network = Network::parse(INPUT).expect("should parse network");
+        let set = NodePredicate::Set(["22C".to_string(), "22Z".to_string()].into());
+        let regex = NodePredicate::Regex(Regex::new("^2.Z$").unwrap());
+
+        assert_eq!(
+            Ok(2),
+            network.steps_until("11A", &NodePredicate::label("11Z"))
+        );
+        assert_eq!(
+            Ok(3),
+            network.steps_until("22A", &NodePredicate::suffix('Z'))
+        );
+        assert_eq!(Ok(2), network.steps_until("22A", &set));
+        assert_eq!(Ok(3), network.steps_until("22A", &regex));
+        assert_eq!(
+            Ok(0),
+            network.steps_until("XXX", &NodePredicate::label("XXX"))
+        );
+    }
+
+    #[test]
+    fn steps_until_matches_reachable_ends() {
+        let network = Network::parse(INPUT).expect("should parse network");
+        let predicates = [
+            NodePredicate::suffix('Z'),
+            NodePredicate::suffix('B'),
+            NodePredicate::label("XXX"),
+        ];
+
+        for from in ["11A", "22A"] {
+            for predicate in &predicates {
+                let first = network
+                    .reachable_ends(from, predicate)
+                    .expect("should walk")
+                    .iter()
+                    .map(|&(_, step)| step)
+                    .min();
+                assert_eq!(first, network.steps_until(from, predicate).ok());
+            }
+        }
+    }
+
+    #[test]
+    fn stops_at_the_first_match() {
+        // one step to the target, then a loop far longer than the answer
+        let size = 100_000;
+        let mut input = format!(
+            "{}\n\nAAA = (ZZZ, ZZZ)\nZZZ = (N1, N1)\n",
+            "L".repeat(10_000)
+        );
+        for n in 1..size {
+            let next = if n + 1 == size {
+                "AAA".to_string()
+            } else {
+                format!("N{}", n + 1)
+            };
+            input.push_str(&format!("N{n} = ({next}, {next})\n"));
+        }
+        let network = Network::parse(&input).expect("should parse network");
+
+        assert_eq!(
+            Ok(1),
+            network.steps_until("AAA", &NodePredicate::label("ZZZ"))
+        );
+        assert_eq!(
+            Ok(3),
+            network.steps_until("AAA", &NodePredicate::label("N2"))
+        );
+    }
+
+    #[test]
+    fn reports_unreachable_targets() {
+        let network = Network::parse(INPUT).expect("should parse network");
+
+        assert_eq!(
+            Err(QueryError::Unreachable {
+                from: "11A".to_string()
+            }),
+            network.steps_until("11A", &NodePredicate::label("22Z"))
+        );
+        assert_eq!(
+            Err(QueryError::UnknownNode("33A".to_string())),
+            network.steps_until("33A", &NodePredicate::label("22Z"))
+        );
+        assert_eq!(Ok(false), network.reaches("22A", "11B"));
+        assert_eq!(Ok(true), network.reaches("22A", "22Z"));
+        assert!(network.reaches("22A", "33Z").is_err());
+    }
+
+    #[test]
+    fn lists_reachable_ends() {
+        let network = Network::parse(INPUT).expect("should parse network");
+        let any = NodePredicate::Regex(Regex::new(".").unwrap());
+
+        assert_eq!(
+            Ok(vec![
+                ("22A".to_string(), 0),
+                ("22B".to_string(), 1),
+                ("22C".to_string(), 2),
+                ("22Z".to_string(), 3),
+            ]),
+            network.reachable_ends("22A", &any)
+        );
+        assert_eq!(
+            Ok(vec![("11Z".to_string(), 2)]),
+            network.reachable_ends("11A", &NodePredicate::suffix('Z'))
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use num::{
+    rational::Ratio, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Integer, One,
+    Zero,
+};
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtrapolateError {
+    InvalidTerm(String),
+    EmptySequence,
+    NotPolynomial,
+    Overflow,
+}
+
+impl fmt::Display for ExtrapolateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ExtrapolateError::*;
+        match self {
+            InvalidTerm(term) => write!(f, "invalid sequence term `{term}`"),
+            EmptySequence => write!(f, "sequence should have at least one term"),
+            NotPolynomial => write!(
+                f,
+                "sequence differences never reach all zeroes, it isn't a polynomial"
+            ),
+            Overflow => write!(f, "value doesn't fit in the chosen integer type"),
+        }
+    }
+}
+
+impl Error for ExtrapolateError {}
+
+/// Integer types sequences can be extrapolated in, e.g. `i64`, `i128` or `BigInt`
+pub trait Term:
+    Clone + PartialEq + Zero + One + CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + FromPrimitive
+{
+}
+
+impl<T> Term for T where
+    T: Clone
+        + PartialEq
+        + Zero
+        + One
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + FromPrimitive
+{
+}
+
+/// One sequence per non-empty line, terms separated by whitespace
+pub fn parse_sequences<T: FromStr>(input: &str) -> Result<Vec<Vec<T>>, ExtrapolateError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_whitespace()
+                .map(|term| {
+                    term.parse()
+                        .map_err(|_| ExtrapolateError::InvalidTerm(term.to_string()))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+///
+/// A sequence fitted as a polynomial in Newton forward-difference form
+///
+/// - `leading`: first value of each difference row, `Δ^k f(0)`, stopping
+///   before the first all-zero row
+/// - term `x` is `sum(C(x, k) * Δ^k f(0))`, so any term costs `O(degree)`
+///   without building the rows out to it
+///
+/// A sequence only counts as polynomial if an all-zero row shows up while
+/// there are still values left to difference. Running out of values first
+/// means the sequence is fitted by no polynomial of lower degree than its
+/// length, so its next term is a guess.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extrapolation<T> {
+    pub leading: Vec<T>,
+    pub len: usize,
+}
+
+impl<T: Term> Extrapolation<T> {
+    pub fn fit(terms: &[T]) -> Result<Extrapolation<T>, ExtrapolateError> {
+        if terms.is_empty() {
+            return Err(ExtrapolateError::EmptySequence);
+        }
+
+        // difference one row in place at a time
+        let mut row = terms.to_vec();
+        let mut leading = vec![];
+        loop {
+            if row.is_empty() {
+                return Err(ExtrapolateError::NotPolynomial);
+            }
+            if row.iter().all(Zero::is_zero) {
+                break;
+            }
+
+            leading.push(row[0].clone());
+            for i in 0..row.len() - 1 {
+                row[i] = row[i + 1]
+                    .checked_sub(&row[i])
+                    .ok_or(ExtrapolateError::Overflow)?;
+            }
+            row.pop();
+        }
+
+        Ok(Extrapolation {
+            leading,
+            len: terms.len(),
+        })
+    }
+
+    /// Degree of the fitted polynomial, `0` for constant (and all-zero) sequences
+    pub fn degree(&self) -> usize {
+        self.leading.len().saturating_sub(1)
+    }
+
+    /// Evaluates the fitted polynomial at `x`, building `C(x, k)` from `C(x, k - 1)`
+    fn evaluate(&self, x: &T) -> Result<T, ExtrapolateError> {
+        let overflow = || ExtrapolateError::Overflow;
+
+        let mut total = T::zero();
+        let mut binomial = T::one();
+        for (k, delta) in self.leading.iter().enumerate() {
+            if k > 0 {
+                // `C(x, k - 1) * (x - k + 1)` is always a multiple of `k`
+                let below = T::from_usize(k - 1).ok_or_else(overflow)?;
+                let k = T::from_usize(k).ok_or_else(overflow)?;
+                binomial = x
+                    .checked_sub(&below)
+                    .and_then(|factor| binomial.checked_mul(&factor))
+                    .and_then(|product| product.checked_div(&k))
+                    .ok_or_else(overflow)?;
+            }
+            // every later `C(x, k)` is zero too once `x` is a smaller natural number
+            if binomial.is_zero() {
+                break;
+            }
+
+            let term = binomial.checked_mul(delta).ok_or_else(overflow)?;
+            total = total.checked_add(&term).ok_or_else(overflow)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Term at zero-based `index`, which may be far past the end of the sequence
+    pub fn term(&self, index: u64) -> Result<T, ExtrapolateError> {
+        self.evaluate(&T::from_u64(index).ok_or(ExtrapolateError::Overflow)?)
+    }
+
+    /// Term at `index`, which may be negative to extrapolate before the first term
+    pub fn at(&self, index: i64) -> Result<T, ExtrapolateError> {
+        self.evaluate(&T::from_i64(index).ok_or(ExtrapolateError::Overflow)?)
+    }
+
+    /// Term after the last one
+    pub fn next(&self) -> Result<T, ExtrapolateError> {
+        self.term(self.len as u64)
+    }
+
+    /// Term before the first one
+    pub fn previous(&self) -> Result<T, ExtrapolateError> {
+        self.at(-1)
+    }
+}
+
+impl<T: Term + Integer> Extrapolation<T> {
+    ///
+    /// Coefficients of the fitted polynomial in `x`, lowest power first
+    ///
+    /// Integer sequences can still need fractional coefficients, e.g. the
+    /// triangular numbers are `x^2 / 2 + x / 2`. Everything is scaled by
+    /// `degree!` while expanding `C(x, k) = x(x - 1)..(x - k + 1) / k!`, so the
+    /// arithmetic stays in integers until the final reduced ratios.
+    ///
+    pub fn coefficients(&self) -> Result<Vec<Ratio<T>>, ExtrapolateError> {
+        let overflow = || ExtrapolateError::Overflow;
+        let from_usize = |value: usize| T::from_usize(value).ok_or_else(overflow);
+
+        let mut denominator = T::one();
+        for k in 1..=self.degree() {
+            denominator = denominator
+                .checked_mul(&from_usize(k)?)
+                .ok_or_else(overflow)?;
+        }
+
+        let mut numerators = vec![T::zero(); self.degree() + 1];
+        // coefficients of the falling factorial `x(x - 1)..(x - k + 1)`
+        let mut falling = vec![T::one()];
+        let mut factorial = T::one();
+        for (k, delta) in self.leading.iter().enumerate() {
+            if k > 0 {
+                factorial = factorial
+                    .checked_mul(&from_usize(k)?)
+                    .ok_or_else(overflow)?;
+
+                // multiply by `x - (k - 1)`
+                let shift = from_usize(k - 1)?;
+                let mut next = vec![T::zero(); falling.len() + 1];
+                for (i, coefficient) in falling.iter().enumerate() {
+                    next[i + 1] = next[i + 1].checked_add(coefficient).ok_or_else(overflow)?;
+                    next[i] = coefficient
+                        .checked_mul(&shift)
+                        .and_then(|shifted| next[i].checked_sub(&shifted))
+                        .ok_or_else(overflow)?;
+                }
+                falling = next;
+            }
+
+            // `delta * C(x, k)` scaled up by `degree!`
+            let scale = denominator
+                .checked_div(&factorial)
+                .and_then(|scale| scale.checked_mul(delta))
+                .ok_or_else(overflow)?;
+            for (numerator, coefficient) in numerators.iter_mut().zip(&falling) {
+                *numerator = coefficient
+                    .checked_mul(&scale)
+                    .and_then(|term| numerator.checked_add(&term))
+                    .ok_or_else(overflow)?;
+            }
+        }
+
+        Ok(numerators
+            .into_iter()
+            .map(|numerator| Ratio::new(numerator, denominator.clone()))
+            .collect())
+    }
+}
+
+/// Value at `index` of the polynomial fitted to `terms`, e.g. `len + 10` or `-5`
+pub fn predict<T: Term>(terms: &[T], index: i64) -> Result<T, ExtrapolateError> {
+    Extrapolation::fit(terms)?.at(index)
+}
+
+/// Sums every sequence's prediction at the index `index(len)` picks for it
+pub fn sum_predictions(input: &str, index: impl Fn(usize) -> i64) -> Result<i64, ExtrapolateError> {
+    parse_sequences::<i64>(input)?
+        .iter()
+        .try_fold(0i64, |sum, sequence| {
+            let value = predict(sequence, index(sequence.len()))?;
+            sum.checked_add(value).ok_or(ExtrapolateError::Overflow)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigInt;
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn extrapolates_example() {
+        let sequences: Vec<Vec<i64>> = parse_sequences(EXAMPLE).expect("should parse");
+        let fits: Vec<Extrapolation<i64>> = sequences
+            .iter()
+            .map(|sequence| Extrapolation::fit(sequence).expect("should fit"))
+            .collect();
+
+        let degrees: Vec<usize> = fits.iter().map(Extrapolation::degree).collect();
+        let next: Vec<i64> = fits.iter().map(|fit| fit.next().unwrap()).collect();
+        let previous: Vec<i64> = fits.iter().map(|fit| fit.previous().unwrap()).collect();
+
+        assert_eq!(vec![1, 2, 3], degrees);
+        assert_eq!(vec![18, 28, 68], next);
+        assert_eq!(vec![-3, 0, 5], previous);
+        // triangular numbers, `(x + 1)(x + 2) / 2`
+        assert_eq!(Ok(499_500), fits[1].term(998));
+    }
+
+    #[test]
+    fn extrapolates_high_degree_sequences() {
+        // `x^20` overflows `i32` by the third term
+        let terms: Vec<i128> = (0..22).map(|x: i128| x.pow(20)).collect();
+        let fit = Extrapolation::fit(&terms).expect("should fit");
+
+        assert_eq!(20, fit.degree());
+        assert_eq!(Ok(22i128.pow(20)), fit.next());
+        assert_eq!(Ok(1), fit.previous());
+
+        let terms: Vec<BigInt> = (0..32).map(|x: u32| BigInt::from(x).pow(30)).collect();
+        let fit = Extrapolation::fit(&terms).expect("should fit");
+
+        assert_eq!(Ok(BigInt::from(1000).pow(30)), fit.term(1000));
+    }
+
+    #[test]
+    fn predicts_any_index() {
+        let sequence: Vec<i64> = vec![10, 13, 16, 21, 30, 45];
+
+        assert_eq!(Ok(68), predict(&sequence, 6));
+        assert_eq!(Ok(5), predict(&sequence, -1));
+        assert_eq!(Ok(1178), predict(&sequence, 16));
+        assert_eq!(Ok(-75), predict(&sequence, -5));
+        assert_eq!(Ok(114), sum_predictions(EXAMPLE, |len| len as i64));
+        assert_eq!(Ok(2), sum_predictions(EXAMPLE, |_| -1));
+    }
+
+    #[test]
+    fn fits_coefficients() {
+        let fit = Extrapolation::fit(&[10i64, 13, 16, 21, 30, 45]).expect("should fit");
+        let expected = vec![
+            Ratio::from_integer(10),
+            Ratio::new(11, 3),
+            Ratio::from_integer(-1),
+            Ratio::new(1, 3),
+        ];
+
+        assert_eq!(Ok(expected), fit.coefficients());
+
+        let fit = Extrapolation::fit(&[0i64, 0, 0]).expect("should fit");
+        assert_eq!(Ok(vec![Ratio::from_integer(0)]), fit.coefficients());
+    }
+
+    #[test]
+    fn reports_bad_sequences() {
+        use ExtrapolateError::*;
+
+        let doubling: Vec<i64> = (0..10).map(|x| 1 << x).collect();
+        let wide: Vec<i64> = vec![0, i64::MAX / 2, i64::MAX / 2 * 2];
+
+        assert_eq!(Err(NotPolynomial), Extrapolation::fit(&doubling));
+        assert_eq!(Err(EmptySequence), Extrapolation::<i64>::fit(&[]));
+        assert_eq!(
+            Err(Overflow),
+            Extrapolation::fit(&wide).expect("should fit").next()
+        );
+        assert_eq!(
+            Err(InvalidTerm("x".to_string())),
+            parse_sequences::<i64>("1 2 x")
+        );
+    }
+}
@@ -15,5 +15,12 @@ pub fn part2(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, part1, part2);
+pub fn part2_a(c: &mut Criterion) {
+    let input = black_box(include_str!("../input/input1.txt"));
+    c.bench_function("day_10::part2_a", |b| {
+        b.iter(|| part2_a::run(input).expect("should benchmark part2_a"))
+    });
+}
+
+criterion_group!(benches, part1, part2, part2_a);
 criterion_main!(benches);
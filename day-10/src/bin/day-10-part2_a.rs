@@ -0,0 +1,10 @@
+use day_10::part2_a::run;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("../../input/input1.txt");
+    let result = run(input)?;
+
+    println!("{:?}", result);
+    Ok(())
+}
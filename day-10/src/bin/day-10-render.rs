@@ -0,0 +1,37 @@
+use day_10::{maze::Grid, render::Picture};
+use std::{env, error::Error, fs};
+
+///
+/// Prints the maze in colour, optionally writing an SVG of it too
+///
+/// ```text
+/// cargo run --bin day-10-render -- [--svg out.svg] [input path]
+/// ```
+///
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut svg_path = None;
+    let mut input_path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--svg" => svg_path = Some(args.next().ok_or("`--svg` needs a path")?),
+            _ if input_path.is_none() => input_path = Some(arg),
+            _ => return Err(format!("unexpected argument `{arg}`").into()),
+        }
+    }
+
+    let input = match input_path {
+        Some(path) => fs::read_to_string(path)?,
+        None => include_str!("../../input/input1.txt").to_string(),
+    };
+
+    let grid = Grid::parse(&input)?;
+    let picture = Picture::new(&grid, &grid.trace()?);
+
+    println!("{}", picture.text(true));
+    if let Some(path) = svg_path {
+        fs::write(path, picture.svg())?;
+    }
+    Ok(())
+}
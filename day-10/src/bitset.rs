@@ -0,0 +1,66 @@
+/// Fixed-size set of `usize` indices, one bit per index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> BitSet {
+        BitSet {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Number of indices the set can hold
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds `index`, returning whether it was newly added
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+
+        added
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Number of indices in the set
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_across_words() {
+        let mut set = BitSet::new(130);
+
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(129));
+        assert!(!set.insert(64));
+
+        assert!(set.contains(129));
+        assert!(!set.contains(1));
+        assert!(!set.contains(500));
+        assert_eq!(3, set.count());
+        assert_eq!(130, set.len());
+    }
+}
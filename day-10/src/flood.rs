@@ -0,0 +1,192 @@
+use crate::{
+    bitset::BitSet,
+    maze::{connections, Direction, Grid, PipeLoop, Point},
+};
+use std::collections::VecDeque;
+
+/// Connected group of tiles that aren't on the loop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Tiles in the region
+    pub size: usize,
+    /// Whether the region is sealed off from the edge of the grid by the loop
+    pub inside: bool,
+}
+
+///
+/// Regions of the maze found by flood filling around the loop
+///
+/// The grid is doubled in resolution, with a one-cell border: tile `(x, y)`
+/// sits at `(2x + 1, 2y + 1)` and the cells between tiles are gaps. Loop
+/// tiles are walls, and so is the gap between two loop pipes that join. Any
+/// other gap stays open, which is how the flood squeezes between parallel
+/// pipes. Regions that reach the border are outside the loop.
+///
+/// - `regions`: every region, in the order their first tile is scanned
+/// - `labels`: region index of each tile, `None` for loop tiles
+///
+#[derive(Debug, Clone)]
+pub struct FloodFill {
+    pub regions: Vec<Region>,
+    pub labels: Vec<Option<usize>>,
+    width: usize,
+}
+
+impl FloodFill {
+    pub fn new(grid: &Grid, pipe_loop: &PipeLoop) -> FloodFill {
+        let (width, height) = (grid.width * 2 + 1, grid.height * 2 + 1);
+        let cell = |x: usize, y: usize| x + y * width;
+
+        let mut walls = BitSet::new(width * height);
+        for &point in &pipe_loop.path {
+            let (x, y) = (point.x * 2 + 1, point.y * 2 + 1);
+            walls.insert(cell(x, y));
+            for direction in connections(pipe_loop.tile(grid, point)) {
+                let gap = match direction {
+                    Direction::Up => cell(x, y - 1),
+                    Direction::Right => cell(x + 1, y),
+                    Direction::Down => cell(x, y + 1),
+                    Direction::Left => cell(x - 1, y),
+                };
+                walls.insert(gap);
+            }
+        }
+
+        let mut regions = vec![];
+        let mut labels = vec![None; grid.tiles.len()];
+        let mut seen = walls.clone();
+        let mut queue = VecDeque::new();
+
+        for i in 0..grid.tiles.len() {
+            let (x, y) = ((i % grid.width) * 2 + 1, (i / grid.width) * 2 + 1);
+            if !seen.insert(cell(x, y)) {
+                continue;
+            }
+
+            let id = regions.len();
+            let mut region = Region {
+                size: 0,
+                inside: true,
+            };
+
+            queue.push_back((x, y));
+            while let Some((x, y)) = queue.pop_front() {
+                if x % 2 == 1 && y % 2 == 1 {
+                    labels[x / 2 + (y / 2) * grid.width] = Some(id);
+                    region.size += 1;
+                }
+                if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                    region.inside = false;
+                }
+
+                let neighbours = [
+                    (x > 0).then(|| (x - 1, y)),
+                    (y > 0).then(|| (x, y - 1)),
+                    (x + 1 < width).then_some((x + 1, y)),
+                    (y + 1 < height).then_some((x, y + 1)),
+                ];
+                for (x, y) in neighbours.into_iter().flatten() {
+                    if seen.insert(cell(x, y)) {
+                        queue.push_back((x, y));
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        FloodFill {
+            regions,
+            labels,
+            width: grid.width,
+        }
+    }
+
+    /// Region the tile at `point` belongs to, `None` on the loop
+    pub fn region(&self, point: Point) -> Option<&Region> {
+        self.labels[point.x + point.y * self.width].map(|id| &self.regions[id])
+    }
+
+    /// Tiles enclosed by the loop
+    pub fn inside_count(&self) -> usize {
+        self.regions
+            .iter()
+            .filter(|region| region.inside)
+            .map(|region| region.size)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part2::find_interiors;
+
+    fn flood(input: &str) -> (Grid, PipeLoop, FloodFill) {
+        let grid = Grid::parse(input).expect("should parse grid");
+        let pipe_loop = grid.trace().expect("should find loop");
+        let flood = FloodFill::new(&grid, &pipe_loop);
+        (grid, pipe_loop, flood)
+    }
+
+    #[test]
+    fn squeezes_between_pipes() {
+        let input = "\
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+        let (_, _, flood) = flood(input);
+
+        let inside: Vec<usize> = flood
+            .regions
+            .iter()
+            .filter(|region| region.inside)
+            .map(|region| region.size)
+            .collect();
+        // both pockets join up through the channel inside the doubled walls
+        assert_eq!(vec![4], inside);
+
+        // the middle is reached by squeezing between `||` and `7F`
+        let label = |x: usize, y: usize| flood.labels[x + y * 10];
+        assert_eq!(label(0, 0), label(3, 3));
+        assert_eq!(label(2, 6), label(7, 6));
+        assert_eq!(None, label(1, 1));
+        assert_eq!(
+            Some(&Region {
+                size: 4,
+                inside: true
+            }),
+            flood.region(Point { x: 2, y: 6 })
+        );
+    }
+
+    #[test]
+    fn matches_scanline() {
+        let inputs = [
+            "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+            include_str!("../input/input1.txt"),
+        ];
+
+        for input in inputs {
+            let (grid, pipe_loop, flood) = flood(input);
+            assert_eq!(find_interiors(&grid, &pipe_loop), flood.inside_count());
+        }
+    }
+}
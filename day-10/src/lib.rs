@@ -1,2 +1,8 @@
+pub mod bitset;
+pub mod flood;
+pub mod maze;
 pub mod part1;
 pub mod part2;
+pub mod part2_a;
+pub mod render;
+pub mod validate;
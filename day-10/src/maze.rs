@@ -0,0 +1,373 @@
+use crate::bitset::BitSet;
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MazeError {
+    MissingStart,
+    MultipleStarts(Vec<Point>),
+    RaggedRow { row: usize, width: usize },
+    NoClosedLoop,
+    DanglingEnd { at: Point, direction: Direction },
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MazeError::*;
+        match self {
+            MissingStart => write!(f, "maze should have a start tile `S`"),
+            RaggedRow { row, width } => {
+                write!(f, "row {row} should be {width} tiles wide like the first")
+            }
+            MultipleStarts(starts) => write!(f, "maze has {} start tiles", starts.len()),
+            NoClosedLoop => write!(f, "no loop of pipes closes through `S`"),
+            DanglingEnd { at, direction } => write!(
+                f,
+                "pipe at ({}, {}) opens {direction:?} onto nothing that connects back",
+                at.x, at.y
+            ),
+        }
+    }
+}
+
+impl Error for MazeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn opposite(self) -> Direction {
+        use Direction::*;
+        match self {
+            Up => Down,
+            Right => Left,
+            Down => Up,
+            Left => Right,
+        }
+    }
+}
+
+/// Directions a tile's pipe opens towards, `S` opens every way
+pub fn connections(tile: char) -> &'static [Direction] {
+    use Direction::*;
+    match tile {
+        '|' => &[Up, Down],
+        '-' => &[Left, Right],
+        'L' => &[Up, Right],
+        'J' => &[Up, Left],
+        '7' => &[Left, Down],
+        'F' => &[Right, Down],
+        'S' => &[Up, Right, Down, Left],
+        _ => &[],
+    }
+}
+
+/// Pipe tile opening towards both `a` and `b`
+pub fn shape(a: Direction, b: Direction) -> Option<char> {
+    "|-LJ7F".chars().find(|&tile| {
+        let ends = connections(tile);
+        a != b && ends.contains(&a) && ends.contains(&b)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+///
+/// Tiles of the pipe maze, row by row
+///
+/// - `tiles`: one `char` per tile, `tiles[x + y * width]`
+/// - `start`: where the `S` tile is
+///
+#[derive(Debug, Clone)]
+pub struct Grid {
+    pub tiles: Vec<char>,
+    pub width: usize,
+    pub height: usize,
+    pub start: Point,
+}
+
+impl Grid {
+    pub fn parse(input: &str) -> Result<Grid, MazeError> {
+        let rows: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        let mut tiles = Vec::with_capacity(width * rows.len());
+        for (row, line) in rows.iter().enumerate() {
+            let len = tiles.len();
+            tiles.extend(line.chars());
+            if tiles.len() - len != width {
+                return Err(MazeError::RaggedRow {
+                    row: row + 1,
+                    width,
+                });
+            }
+        }
+
+        Grid::from_tiles(tiles, width, rows.len())
+    }
+
+    /// Builds a grid from full rows of tiles, which should hold exactly one `S`
+    pub fn from_tiles(tiles: Vec<char>, width: usize, height: usize) -> Result<Grid, MazeError> {
+        let starts = starts(&tiles, width);
+        let start = match starts[..] {
+            [] => return Err(MazeError::MissingStart),
+            [start] => start,
+            _ => return Err(MazeError::MultipleStarts(starts)),
+        };
+
+        Ok(Grid {
+            tiles,
+            width,
+            height,
+            start,
+        })
+    }
+
+    pub fn index(&self, point: Point) -> usize {
+        point.x + point.y * self.width
+    }
+
+    pub fn tile(&self, point: Point) -> char {
+        self.tiles[self.index(point)]
+    }
+
+    /// The next tile over in `direction`, if it's inside the grid
+    pub fn neighbour(&self, point: Point, direction: Direction) -> Option<Point> {
+        use Direction::*;
+        let Point { x, y } = point;
+        match direction {
+            Up if y > 0 => Some(Point { x, y: y - 1 }),
+            Down if y + 1 < self.height => Some(Point { x, y: y + 1 }),
+            Left if x > 0 => Some(Point { x: x - 1, y }),
+            Right if x + 1 < self.width => Some(Point { x: x + 1, y }),
+            _ => None,
+        }
+    }
+
+    /// Whether the tile at `point` opens towards `direction`
+    pub fn connects(&self, point: Point, direction: Direction) -> bool {
+        connections(self.tile(point)).contains(&direction)
+    }
+
+    ///
+    /// Follows the loop once around from `S`, in a single direction
+    ///
+    /// `S` could be any pipe, so the walk leaves towards each neighbour that
+    /// opens back towards it in turn. Each walk keeps taking whichever end of
+    /// a pipe it didn't come in through, until it's back at `S` or a pipe
+    /// opens onto a tile that doesn't connect back. The first walk to make
+    /// it home is the loop, and `S` is the pipe joining the direction it left
+    /// in and the direction it came home from.
+    ///
+    pub fn trace(&self) -> Result<PipeLoop, MazeError> {
+        let mut dangling = None;
+        for direction in Direction::ALL {
+            let connected = self
+                .neighbour(self.start, direction)
+                .is_some_and(|next| self.connects(next, direction.opposite()));
+            if !connected {
+                continue;
+            }
+
+            match self.walk(direction) {
+                Ok(pipe_loop) => return Ok(pipe_loop),
+                Err(err) => {
+                    dangling.get_or_insert(err);
+                }
+            }
+        }
+
+        Err(dangling.unwrap_or(MazeError::NoClosedLoop))
+    }
+
+    /// Walks the loop leaving `S` towards `first`, see [`Grid::trace`]
+    pub fn walk(&self, first: Direction) -> Result<PipeLoop, MazeError> {
+        let start = self.start;
+
+        let mut path = vec![start];
+        let mut cells = BitSet::new(self.tiles.len());
+        cells.insert(self.index(start));
+
+        let mut point = start;
+        let mut heading = first;
+        loop {
+            let next = self
+                .neighbour(point, heading)
+                .filter(|&next| self.connects(next, heading.opposite()))
+                .ok_or(MazeError::DanglingEnd {
+                    at: point,
+                    direction: heading,
+                })?;
+
+            if next == start {
+                break;
+            }
+            // pipes only have two ends, so a walk can't cross itself
+            cells.insert(self.index(next));
+            path.push(next);
+
+            let came_from = heading.opposite();
+            heading = connections(self.tile(next))
+                .iter()
+                .copied()
+                .find(|&end| end != came_from)
+                .expect("loop pipes should have two ends");
+            point = next;
+        }
+
+        Ok(PipeLoop {
+            path,
+            cells,
+            start_shape: shape(first, heading.opposite())
+                .expect("walk should leave and return apart"),
+        })
+    }
+}
+
+/// Every `S` tile in row-major order
+pub fn starts(tiles: &[char], width: usize) -> Vec<Point> {
+    tiles
+        .iter()
+        .enumerate()
+        .filter(|(_, &tile)| tile == 'S')
+        .map(|(i, _)| Point {
+            x: i % width,
+            y: i / width,
+        })
+        .collect()
+}
+
+///
+/// The loop through `S`
+///
+/// - `path`: every loop tile in walking order, starting at `S`
+/// - `cells`: grid indices of the loop tiles
+/// - `start_shape`: the pipe hidden under `S`
+///
+#[derive(Debug, Clone)]
+pub struct PipeLoop {
+    pub path: Vec<Point>,
+    pub cells: BitSet,
+    pub start_shape: char,
+}
+
+impl PipeLoop {
+    pub fn contains(&self, grid: &Grid, point: Point) -> bool {
+        self.cells.contains(grid.index(point))
+    }
+
+    /// Tile at `point`, with `S` swapped for the pipe it stands in for
+    pub fn tile(&self, grid: &Grid, point: Point) -> char {
+        if point == grid.start {
+            self.start_shape
+        } else {
+            grid.tile(point)
+        }
+    }
+
+    /// Steps along the loop to the tile farthest from `S`
+    pub fn farthest(&self) -> usize {
+        self.path.len() / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traces_loop_in_order() {
+        let input = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+        let grid = Grid::parse(input).expect("should parse grid");
+        let pipe_loop = grid.trace().expect("should find loop");
+
+        let path: Vec<(usize, usize)> = pipe_loop
+            .path
+            .iter()
+            .map(|point| (point.x, point.y))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (1, 1),
+                (2, 1),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (1, 2)
+            ],
+            path
+        );
+        assert_eq!(8, pipe_loop.cells.count());
+        assert_eq!('F', pipe_loop.start_shape);
+        assert_eq!(4, pipe_loop.farthest());
+        assert!(!pipe_loop.contains(&grid, Point { x: 2, y: 2 }));
+    }
+
+    #[test]
+    fn reports_broken_mazes() {
+        use MazeError::*;
+
+        assert_eq!(MissingStart, Grid::parse("...\n.F.").unwrap_err());
+        assert_eq!(
+            RaggedRow { row: 2, width: 3 },
+            Grid::parse("S-7\n|").unwrap_err()
+        );
+
+        assert_eq!(
+            MultipleStarts(vec![Point { x: 0, y: 0 }, Point { x: 2, y: 1 }]),
+            Grid::parse("S-7\n|.S").unwrap_err()
+        );
+
+        let grid = Grid::parse(".S-7.\n.|.|.\n.L-..").expect("should parse grid");
+        assert_eq!(
+            DanglingEnd {
+                at: Point { x: 3, y: 1 },
+                direction: Direction::Down
+            },
+            grid.trace().unwrap_err()
+        );
+
+        let grid = Grid::parse(".....\n.S.7.\n.....").expect("should parse grid");
+        assert_eq!(NoClosedLoop, grid.trace().unwrap_err());
+    }
+
+    #[test]
+    fn infers_start_shape() {
+        // the pipe above `S` opens towards it, but leads off the grid
+        let input = "\
+.|...
+.S-7.
+.|.|.
+.L-J.";
+
+        let grid = Grid::parse(input).expect("should parse grid");
+        let pipe_loop = grid.trace().expect("should find loop");
+
+        assert_eq!('F', pipe_loop.start_shape);
+        assert_eq!(8, pipe_loop.path.len());
+    }
+}
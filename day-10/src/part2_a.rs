@@ -0,0 +1,111 @@
+use crate::maze::{Grid, Point};
+use std::error::Error;
+
+/// Twice the area of the polygon whose corners are `path`, by the shoelace formula
+pub fn twice_area(path: &[Point]) -> usize {
+    let sum: i64 = path
+        .iter()
+        .zip(path.iter().cycle().skip(1))
+        .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+        .sum();
+
+    sum.unsigned_abs() as usize
+}
+
+///
+/// Counts interiors from the loop's area with Pick's theorem
+///
+/// Treating each loop tile's centre as a lattice point on the polygon's
+/// boundary, `area = interior + boundary / 2 - 1`. Every boundary point is
+/// a loop tile and every interior point is an enclosed tile.
+///
+pub fn pick_interiors(path: &[Point]) -> usize {
+    (twice_area(path) + 2).saturating_sub(path.len()) / 2
+}
+
+///
+/// - trace the loop once from `S`
+/// - shoelace the loop's area, then Pick's theorem for the tiles inside
+///
+pub fn run(input: &str) -> Result<String, Box<dyn Error>> {
+    let input = normalize::input::text(input).warn();
+    let grid = Grid::parse(&input)?;
+    let pipe_loop = grid.trace()?;
+
+    Ok(pick_interiors(&pipe_loop.path).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part2::find_interiors;
+
+    const EXAMPLES: [(&str, &str); 3] = [
+        (
+            "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+            "4",
+        ),
+        (
+            "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+            "8",
+        ),
+        (
+            "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+            "10",
+        ),
+    ];
+
+    #[test]
+    fn example_inputs() {
+        for (input, expected) in EXAMPLES {
+            assert_eq!(expected, run(input).expect("should return expected value"));
+        }
+    }
+
+    #[test]
+    fn matches_scanline() {
+        let inputs = EXAMPLES
+            .iter()
+            .map(|(input, _)| *input)
+            .chain([include_str!("../input/input1.txt")]);
+
+        for input in inputs {
+            let grid = Grid::parse(input).expect("should parse grid");
+            let pipe_loop = grid.trace().expect("should find loop");
+
+            assert_eq!(
+                find_interiors(&grid, &pipe_loop),
+                pick_interiors(&pipe_loop.path)
+            );
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{
+    maze::{connections, Direction, Grid, PipeLoop, Point},
+    part2::interiors,
+};
+use colored::Colorize;
+
+/// Pixels per tile in SVG output
+pub const TILE_SIZE: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileClass {
+    Loop,
+    Inside,
+    Outside,
+}
+
+/// Unicode box-drawing version of a pipe tile, blank for anything else
+pub fn box_char(tile: char) -> char {
+    match tile {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => ' ',
+    }
+}
+
+///
+/// Everything needed to draw a maze
+///
+/// - `tiles`: each tile's pipe, with `S` swapped for the pipe it stands in
+///   for, and whether it's on the loop, inside it or outside it
+/// - inside and outside come from [`interiors`], so a picture shows
+///   exactly what part 2 counted
+///
+#[derive(Debug, Clone)]
+pub struct Picture {
+    pub width: usize,
+    pub height: usize,
+    pub start: Point,
+    pub tiles: Vec<(char, TileClass)>,
+}
+
+impl Picture {
+    pub fn new(grid: &Grid, pipe_loop: &PipeLoop) -> Picture {
+        let inside = interiors(grid, pipe_loop);
+
+        let tiles = (0..grid.height)
+            .flat_map(|y| (0..grid.width).map(move |x| Point { x, y }))
+            .map(|point| {
+                let class = if pipe_loop.contains(grid, point) {
+                    TileClass::Loop
+                } else if inside.contains(grid.index(point)) {
+                    TileClass::Inside
+                } else {
+                    TileClass::Outside
+                };
+                (pipe_loop.tile(grid, point), class)
+            })
+            .collect();
+
+        Picture {
+            width: grid.width,
+            height: grid.height,
+            start: grid.start,
+            tiles,
+        }
+    }
+
+    fn points(&self) -> impl Iterator<Item = (Point, char, TileClass)> + '_ {
+        self.tiles.iter().enumerate().map(|(i, &(tile, class))| {
+            let point = Point {
+                x: i % self.width,
+                y: i / self.width,
+            };
+            (point, tile, class)
+        })
+    }
+
+    ///
+    /// Draws the maze with box-drawing characters, one line per row
+    ///
+    /// Without colour only the loop's pipes are drawn, on `█` inside and
+    /// `░` outside. With colour the loop is bold (`S` in red), junk pipes are
+    /// dimmed, and inside and outside tiles get green and blue backgrounds.
+    ///
+    pub fn text(&self, colour: bool) -> String {
+        let mut text = String::with_capacity(self.tiles.len() * 4);
+
+        for (point, tile, class) in self.points() {
+            if point.x == 0 && point.y > 0 {
+                text.push('\n');
+            }
+
+            let symbol = box_char(tile);
+            if !colour {
+                text.push(match class {
+                    TileClass::Loop => symbol,
+                    TileClass::Inside => '█',
+                    TileClass::Outside => '░',
+                });
+                continue;
+            }
+
+            let symbol = symbol.to_string();
+            let styled = match class {
+                TileClass::Loop if point == self.start => symbol.bold().red(),
+                TileClass::Loop => symbol.bold().bright_white(),
+                TileClass::Inside => symbol.bright_black().on_green(),
+                TileClass::Outside => symbol.bright_black().on_blue(),
+            };
+            text.push_str(&styled.to_string());
+        }
+
+        text
+    }
+
+    ///
+    /// Draws the same picture as an SVG document
+    ///
+    /// Each tile is a [`TILE_SIZE`] square shaded by its class, with its
+    /// pipe drawn from the square's centre out to each edge it opens onto.
+    ///
+    pub fn svg(&self) -> String {
+        let half = TILE_SIZE / 2;
+        let (width, height) = (self.width * TILE_SIZE, self.height * TILE_SIZE);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+        );
+
+        let mut loop_path = String::new();
+        let mut junk_path = String::new();
+        for (point, tile, class) in self.points() {
+            let (x, y) = (point.x * TILE_SIZE, point.y * TILE_SIZE);
+            let fill = match class {
+                TileClass::Loop => "#ffffff",
+                TileClass::Inside => "#9be39b",
+                TileClass::Outside => "#cfe0f5",
+            };
+            svg.push_str(&format!(
+                "  <rect x=\"{x}\" y=\"{y}\" width=\"{TILE_SIZE}\" height=\"{TILE_SIZE}\" fill=\"{fill}\"/>\n"
+            ));
+
+            let path = match class {
+                TileClass::Loop => &mut loop_path,
+                _ => &mut junk_path,
+            };
+            let (cx, cy) = (x + half, y + half);
+            for direction in connections(tile) {
+                let (ex, ey) = match direction {
+                    Direction::Up => (cx, y),
+                    Direction::Right => (x + TILE_SIZE, cy),
+                    Direction::Down => (cx, y + TILE_SIZE),
+                    Direction::Left => (x, cy),
+                };
+                path.push_str(&format!("M{cx} {cy}L{ex} {ey}"));
+            }
+        }
+
+        svg.push_str(&format!(
+            "  <path d=\"{junk_path}\" stroke=\"#8a8a8a\" stroke-width=\"1\" fill=\"none\"/>\n"
+        ));
+        svg.push_str(&format!(
+            "  <path d=\"{loop_path}\" stroke=\"#202020\" stroke-width=\"3\" stroke-linecap=\"round\" fill=\"none\"/>\n"
+        ));
+        svg.push_str("</svg>");
+
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    fn picture() -> Picture {
+        let grid = Grid::parse(INPUT).expect("should parse grid");
+        let pipe_loop = grid.trace().expect("should find loop");
+        Picture::new(&grid, &pipe_loop)
+    }
+
+    #[test]
+    fn draws_loop_with_box_characters() {
+        let expected = "\
+░░░░░
+░┌─┐░
+░│█│░
+░└─┘░
+░░░░░";
+
+        assert_eq!(expected, picture().text(false));
+    }
+
+    #[test]
+    fn draws_svg() {
+        let svg = picture().svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60\""));
+        assert_eq!(25, svg.matches("<rect").count());
+        assert_eq!(1, svg.matches("fill=\"#9be39b\"").count());
+        // `S` is drawn as the `F` it stands in for
+        assert!(svg.contains("M18 18L24 18M18 18L18 24"));
+        assert!(svg.ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::maze::{starts, Direction, Grid, MazeError};
+use std::{error::Error, fmt};
+
+/// Every problem found while validating a maze
+#[derive(Debug)]
+pub struct ValidationError {
+    pub problems: Vec<MazeError>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.problems.iter().map(|p| p.to_string()).collect();
+        write!(f, "invalid maze:\n{}", lines.join("\n"))
+    }
+}
+
+impl Error for ValidationError {}
+
+///
+/// Collects every problem with a maze, rather than stopping at the first
+///
+/// - rows that aren't as wide as the first, which are padded with `.` so
+///   the loop can still be checked
+/// - no `S`, or more than one (the loop is checked from the first)
+/// - no loop closing through `S`, along with where each walk from `S`
+///   dangled off
+///
+pub fn validate(input: &str) -> Vec<MazeError> {
+    let mut problems = vec![];
+
+    let rows: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    let padded = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut tiles = Vec::with_capacity(padded * rows.len());
+    for (row, line) in rows.iter().enumerate() {
+        let len = line.chars().count();
+        if len != width {
+            problems.push(MazeError::RaggedRow {
+                row: row + 1,
+                width,
+            });
+        }
+        tiles.extend(line.chars().chain(std::iter::repeat('.')).take(padded));
+    }
+
+    let starts = starts(&tiles, padded);
+    let Some(&start) = starts.first() else {
+        problems.push(MazeError::MissingStart);
+        return problems;
+    };
+    if starts.len() > 1 {
+        problems.push(MazeError::MultipleStarts(starts));
+    }
+
+    let grid = Grid {
+        tiles,
+        width: padded,
+        height: rows.len(),
+        start,
+    };
+
+    let walks: Vec<Result<_, MazeError>> = Direction::ALL
+        .into_iter()
+        .filter(|&direction| {
+            grid.neighbour(start, direction)
+                .is_some_and(|next| grid.connects(next, direction.opposite()))
+        })
+        .map(|direction| grid.walk(direction))
+        .collect();
+
+    if !walks.iter().any(Result::is_ok) {
+        problems.push(MazeError::NoClosedLoop);
+        problems.extend(walks.into_iter().filter_map(Result::err));
+    }
+
+    problems
+}
+
+/// Validates a maze, failing with every problem found
+pub fn check(input: &str) -> Result<(), ValidationError> {
+    let problems = validate(input);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError { problems })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::Point;
+
+    #[test]
+    fn accepts_valid_maze() {
+        let input = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+        assert!(check(input).is_ok());
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        use MazeError::*;
+
+        // ragged second row, two starts, and a loop broken at the bottom
+        let input = "\
+.S-7.
+.|.|.S
+.L-..";
+
+        assert_eq!(
+            vec![
+                RaggedRow { row: 2, width: 5 },
+                MultipleStarts(vec![Point { x: 1, y: 0 }, Point { x: 5, y: 1 }]),
+                NoClosedLoop,
+                DanglingEnd {
+                    at: Point { x: 3, y: 1 },
+                    direction: Direction::Down
+                },
+                DanglingEnd {
+                    at: Point { x: 2, y: 2 },
+                    direction: Direction::Right
+                },
+            ],
+            validate(input)
+        );
+        assert_eq!(vec![MissingStart], validate("...\n.F."));
+    }
+}
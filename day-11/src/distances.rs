@@ -0,0 +1,268 @@
+use crate::{
+    expansion::{Expansion, Factor},
+    galaxy::{GalaxyMap, Point},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Steps moving only up, down, left or right
+    Manhattan,
+    /// Steps when diagonal moves are allowed too
+    Chebyshev,
+    /// Straight-line distance
+    Euclidean,
+}
+
+impl Metric {
+    pub fn distance(self, a: Point<f64>, b: Point<f64>) -> f64 {
+        let (dx, dy) = ((a.x - b.x).abs(), (a.y - b.y).abs());
+        match self {
+            Metric::Manhattan => dx + dy,
+            Metric::Chebyshev => dx.max(dy),
+            Metric::Euclidean => dx.hypot(dy),
+        }
+    }
+}
+
+/// Another galaxy and how far away it is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbour {
+    pub index: usize,
+    pub distance: f64,
+}
+
+/// Two galaxies, by index, and the distance between them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pair {
+    pub a: usize,
+    pub b: usize,
+    pub distance: f64,
+}
+
+///
+/// Expanded galaxy positions, for asking about distances between them
+///
+/// Galaxies are referred to by their index in [`GalaxyMap::galaxies`].
+/// Positions are `f64`, so Manhattan and Chebyshev distances stay exact
+/// while they're below `2^53`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sky {
+    pub positions: Vec<Point<f64>>,
+}
+
+fn to_f64(value: Factor) -> f64 {
+    *value.numer() as f64 / *value.denom() as f64
+}
+
+impl Sky {
+    pub fn new(map: &GalaxyMap, expansion: &Expansion) -> Sky {
+        let positions = map
+            .expand_with(expansion)
+            .into_iter()
+            .map(|point| Point {
+                x: to_f64(point.x),
+                y: to_f64(point.y),
+            })
+            .collect();
+
+        Sky { positions }
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn distance(&self, a: usize, b: usize, metric: Metric) -> f64 {
+        metric.distance(self.positions[a], self.positions[b])
+    }
+
+    /// Distance between every two galaxies, `matrix[a][b]`
+    pub fn matrix(&self, metric: Metric) -> Vec<Vec<f64>> {
+        (0..self.len())
+            .map(|a| {
+                (0..self.len())
+                    .map(|b| self.distance(a, b, metric))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The `k` galaxies closest to galaxy `index`, closest first and ties by index
+    pub fn nearest(&self, index: usize, k: usize, metric: Metric) -> Vec<Neighbour> {
+        let mut neighbours: Vec<Neighbour> = (0..self.len())
+            .filter(|&other| other != index)
+            .map(|other| Neighbour {
+                index: other,
+                distance: self.distance(index, other, metric),
+            })
+            .collect();
+
+        neighbours.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        neighbours.truncate(k);
+        neighbours
+    }
+
+    /// Every unordered pair of galaxies
+    pub fn pairs(&self, metric: Metric) -> impl Iterator<Item = Pair> + '_ {
+        (0..self.len()).flat_map(move |a| {
+            (a + 1..self.len()).map(move |b| Pair {
+                a,
+                b,
+                distance: self.distance(a, b, metric),
+            })
+        })
+    }
+
+    /// Closest two galaxies, the first pair found on ties
+    pub fn closest_pair(&self, metric: Metric) -> Option<Pair> {
+        self.pairs(metric).reduce(|best, pair| {
+            if pair.distance < best.distance {
+                pair
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Farthest apart two galaxies, the first pair found on ties
+    pub fn farthest_pair(&self, metric: Metric) -> Option<Pair> {
+        self.pairs(metric).reduce(|best, pair| {
+            if pair.distance > best.distance {
+                pair
+            } else {
+                best
+            }
+        })
+    }
+
+    ///
+    /// Each galaxy's total distance to every other galaxy
+    ///
+    /// Manhattan distances split by axis, so each axis is sorted once and
+    /// summed with prefix sums in `O(n log n)`. Other metrics sum one row at
+    /// a time, `O(n^2)` time but never the whole matrix in memory.
+    ///
+    pub fn sums(&self, metric: Metric) -> Vec<f64> {
+        if metric == Metric::Manhattan {
+            let xs: Vec<f64> = self.positions.iter().map(|point| point.x).collect();
+            let ys: Vec<f64> = self.positions.iter().map(|point| point.y).collect();
+
+            return axis_sums(&xs)
+                .into_iter()
+                .zip(axis_sums(&ys))
+                .map(|(x, y)| x + y)
+                .collect();
+        }
+
+        (0..self.len())
+            .map(|a| (0..self.len()).map(|b| self.distance(a, b, metric)).sum())
+            .collect()
+    }
+}
+
+/// Each value's total `|a - b|` to every other value, in the original order
+fn axis_sums(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let total: f64 = values.iter().sum();
+    let mut before = 0.0;
+    let mut sums = vec![0.0; values.len()];
+    for (i, &index) in order.iter().enumerate() {
+        let value = values[index];
+        let after = total - before - value;
+        let later = (values.len() - i - 1) as f64;
+        sums[index] = value * i as f64 - before + after - value * later;
+        before += value;
+    }
+
+    sums
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    fn sky() -> Sky {
+        Sky::new(&GalaxyMap::parse(INPUT), &Expansion::uniform(2))
+    }
+
+    #[test]
+    fn measures_example_pairs() {
+        let sky = sky();
+
+        // galaxy numbers in the puzzle start at 1
+        assert_eq!(9.0, sky.distance(4, 8, Metric::Manhattan));
+        assert_eq!(15.0, sky.distance(0, 6, Metric::Manhattan));
+        assert_eq!(17.0, sky.distance(2, 5, Metric::Manhattan));
+        assert_eq!(5.0, sky.distance(7, 8, Metric::Manhattan));
+
+        assert_eq!(5.0, sky.distance(4, 8, Metric::Chebyshev));
+        assert_eq!(5.0, sky.distance(7, 8, Metric::Euclidean));
+    }
+
+    #[test]
+    fn summarises_distances() {
+        let sky = sky();
+
+        let matrix = sky.matrix(Metric::Manhattan);
+        assert_eq!(matrix[4][8], matrix[8][4]);
+        assert_eq!(0.0, matrix[3][3]);
+
+        let total: f64 = sky.sums(Metric::Manhattan).iter().sum();
+        assert_eq!(374.0 * 2.0, total);
+
+        // prefix sums and row-by-row sums agree with the matrix
+        for metric in [Metric::Manhattan, Metric::Chebyshev, Metric::Euclidean] {
+            let rows: Vec<f64> = sky
+                .matrix(metric)
+                .iter()
+                .map(|row| row.iter().sum())
+                .collect();
+            assert_eq!(rows, sky.sums(metric));
+        }
+
+        let nearest = sky.nearest(8, 2, Metric::Manhattan);
+        // galaxies 7 and 8 are both 5 away from galaxy 9
+        assert_eq!(
+            vec![
+                Neighbour {
+                    index: 6,
+                    distance: 5.0
+                },
+                Neighbour {
+                    index: 7,
+                    distance: 5.0
+                }
+            ],
+            nearest
+        );
+
+        let closest = sky
+            .closest_pair(Metric::Manhattan)
+            .expect("should have pairs");
+        assert_eq!((1, 3, 5.0), (closest.a, closest.b, closest.distance));
+
+        let farthest = sky
+            .farthest_pair(Metric::Manhattan)
+            .expect("should have pairs");
+        assert_eq!((1, 7, 19.0), (farthest.a, farthest.b, farthest.distance));
+    }
+}
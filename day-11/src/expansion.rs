@@ -0,0 +1,208 @@
+use num::{rational::Ratio, Zero};
+use std::{collections::HashMap, error::Error, fmt};
+
+/// How many lines one empty line becomes, `0` removes it and `1/2` halves it
+pub type Factor = Ratio<i64>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpansionError {
+    MalformedLine(String),
+    InvalidFactor(String),
+    NegativeFactor(String),
+}
+
+impl fmt::Display for ExpansionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ExpansionError::*;
+        match self {
+            MalformedLine(line) => write!(f, "invalid expansion line: `{line}`"),
+            InvalidFactor(factor) => write!(f, "invalid expansion factor `{factor}`"),
+            NegativeFactor(factor) => write!(f, "expansion factor `{factor}` is negative"),
+        }
+    }
+}
+
+impl Error for ExpansionError {}
+
+/// Parses `2`, `1/2` or `0.25` as an exact factor
+pub fn parse_factor(value: &str) -> Result<Factor, ExpansionError> {
+    let invalid = || ExpansionError::InvalidFactor(value.to_string());
+    // decimal halves are plain digits, the sign is only ever read once up front
+    let digits = |part: &str| -> Result<i64, ExpansionError> {
+        if part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        part.parse().map_err(|_| invalid())
+    };
+
+    let factor = match value.split_once('.') {
+        Some((whole, fraction)) => {
+            let (negative, whole) = match whole.strip_prefix('-') {
+                Some(whole) => (true, whole),
+                None => (false, whole),
+            };
+            let scale = 10i64
+                .checked_pow(fraction.len() as u32)
+                .ok_or_else(invalid)?;
+            let (whole, fraction) = (digits(whole)?, digits(fraction)?);
+            let numer = whole
+                .checked_mul(scale)
+                .and_then(|whole| whole.checked_add(fraction))
+                .ok_or_else(invalid)?;
+            let numer = if negative { -numer } else { numer };
+            Ratio::new(numer, scale)
+        }
+        None => value.parse().map_err(|_| invalid())?,
+    };
+
+    if factor < Factor::zero() {
+        return Err(ExpansionError::NegativeFactor(value.to_string()));
+    }
+
+    Ok(factor)
+}
+
+/// Factors for the empty lines along one axis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AxisExpansion {
+    pub default: Factor,
+    /// Factors for particular empty lines, by zero-based index
+    pub lines: HashMap<usize, Factor>,
+}
+
+impl AxisExpansion {
+    pub fn uniform(factor: Factor) -> AxisExpansion {
+        AxisExpansion {
+            default: factor,
+            lines: HashMap::new(),
+        }
+    }
+
+    /// Factor for the empty line at `index`
+    pub fn factor(&self, index: usize) -> Factor {
+        self.lines.get(&index).copied().unwrap_or(self.default)
+    }
+}
+
+///
+/// How much each empty row and column of a galaxy map grows
+///
+/// Lines holding a galaxy always stay one wide, so factors given for them
+/// are ignored.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion {
+    pub rows: AxisExpansion,
+    pub cols: AxisExpansion,
+}
+
+impl Expansion {
+    /// Every empty row and column becomes `factor` lines
+    pub fn uniform(factor: i64) -> Expansion {
+        Expansion::new(Factor::from(factor), Factor::from(factor))
+    }
+
+    pub fn new(rows: Factor, cols: Factor) -> Expansion {
+        Expansion {
+            rows: AxisExpansion::uniform(rows),
+            cols: AxisExpansion::uniform(cols),
+        }
+    }
+
+    ///
+    /// Reads expansion settings from a side file
+    ///
+    /// ```text
+    /// # defaults for every empty row and column
+    /// rows 1000000
+    /// cols 1/2
+    /// # single lines, by zero-based index
+    /// row 3 0
+    /// col 7 2.5
+    /// ```
+    ///
+    /// Anything not given defaults to `1`, i.e. no expansion.
+    ///
+    pub fn parse(input: &str) -> Result<Expansion, ExpansionError> {
+        let mut expansion = Expansion::uniform(1);
+
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || ExpansionError::MalformedLine(line.to_string());
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words[..] {
+                ["rows", factor] => expansion.rows.default = parse_factor(factor)?,
+                ["cols", factor] => expansion.cols.default = parse_factor(factor)?,
+                [axis @ ("row" | "col"), index, factor] => {
+                    let index: usize = index.parse().map_err(|_| malformed())?;
+                    let axis = match axis {
+                        "row" => &mut expansion.rows,
+                        _ => &mut expansion.cols,
+                    };
+                    axis.lines.insert(index, parse_factor(factor)?);
+                }
+                _ => return Err(malformed()),
+            }
+        }
+
+        Ok(expansion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_factors() {
+        assert_eq!(Ok(Factor::from(3)), parse_factor("3"));
+        assert_eq!(Ok(Factor::new(1, 2)), parse_factor("1/2"));
+        assert_eq!(Ok(Factor::new(5, 2)), parse_factor("2.5"));
+        assert_eq!(Ok(Factor::zero()), parse_factor("0"));
+        assert_eq!(
+            Err(ExpansionError::NegativeFactor("-1".to_string())),
+            parse_factor("-1")
+        );
+        assert_eq!(
+            Err(ExpansionError::NegativeFactor("-0.5".to_string())),
+            parse_factor("-0.5")
+        );
+        assert_eq!(
+            Err(ExpansionError::InvalidFactor("1.-5".to_string())),
+            parse_factor("1.-5")
+        );
+        assert_eq!(
+            Err(ExpansionError::InvalidFactor("1.+5".to_string())),
+            parse_factor("1.+5")
+        );
+        assert_eq!(
+            Err(ExpansionError::InvalidFactor("x".to_string())),
+            parse_factor("x")
+        );
+    }
+
+    #[test]
+    fn parses_side_file() {
+        let input = "\
+# contract columns
+rows 10
+cols 1/2
+row 3 0
+col 7 2.5
+";
+
+        let expansion = Expansion::parse(input).expect("should parse expansion");
+
+        assert_eq!(Factor::from(10), expansion.rows.factor(2));
+        assert_eq!(Factor::zero(), expansion.rows.factor(3));
+        assert_eq!(Factor::new(1, 2), expansion.cols.factor(2));
+        assert_eq!(Factor::new(5, 2), expansion.cols.factor(7));
+        assert_eq!(
+            Err(ExpansionError::MalformedLine("row x 2".to_string())),
+            Expansion::parse("row x 2")
+        );
+    }
+}
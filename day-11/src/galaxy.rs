@@ -0,0 +1,241 @@
+use crate::expansion::{Expansion, Factor};
+use num::{One, Zero};
+use std::ops::{Add, Mul, Sub};
+
+pub const GALAXY_CHAR: char = '#';
+
+/// Galaxy position, in whole tiles or in [`Factor`]s once expanded by fractions
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Ord, PartialOrd)]
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+///
+/// Galaxy positions as observed, before any expansion
+///
+/// - `galaxies`: zero-based positions in reading order
+/// - `width`, `height`: size of the observed image
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GalaxyMap {
+    pub galaxies: Vec<Point>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl GalaxyMap {
+    pub fn parse(input: &str) -> GalaxyMap {
+        let rows: Vec<&str> = input.lines().map(str::trim_end).collect();
+
+        let galaxies = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|&(_, char)| char == GALAXY_CHAR)
+                    .map(move |(x, _)| Point {
+                        x: x as i64,
+                        y: y as i64,
+                    })
+            })
+            .collect();
+
+        GalaxyMap {
+            galaxies,
+            width: rows
+                .iter()
+                .map(|row| row.chars().count())
+                .max()
+                .unwrap_or(0),
+            height: rows.len(),
+        }
+    }
+
+    /// Whether each row holds a galaxy
+    pub fn occupied_rows(&self) -> Vec<bool> {
+        let mut occupied = vec![false; self.height];
+        self.galaxies
+            .iter()
+            .for_each(|galaxy| occupied[galaxy.y as usize] = true);
+        occupied
+    }
+
+    /// Whether each column holds a galaxy
+    pub fn occupied_cols(&self) -> Vec<bool> {
+        let mut occupied = vec![false; self.width];
+        self.galaxies
+            .iter()
+            .for_each(|galaxy| occupied[galaxy.x as usize] = true);
+        occupied
+    }
+
+    /// Galaxy positions once every empty row and column is `expansion` wide
+    pub fn expand(&self, expansion: i64) -> Vec<Point> {
+        // whole-number factors only ever land on whole tiles
+        self.expand_with(&Expansion::uniform(expansion))
+            .into_iter()
+            .map(|point| Point {
+                x: point.x.to_integer(),
+                y: point.y.to_integer(),
+            })
+            .collect()
+    }
+
+    /// Galaxy positions with each empty row and column sized by `expansion`
+    pub fn expand_with(&self, expansion: &Expansion) -> Vec<Point<Factor>> {
+        let rows = expanded_offsets(&self.occupied_rows(), |y| expansion.rows.factor(y));
+        let cols = expanded_offsets(&self.occupied_cols(), |x| expansion.cols.factor(x));
+
+        self.galaxies
+            .iter()
+            .map(|galaxy| Point {
+                x: cols[galaxy.x as usize],
+                y: rows[galaxy.y as usize],
+            })
+            .collect()
+    }
+}
+
+/// Where each line starts once the empty line at `i` is `factor(i)` wide
+fn expanded_offsets<T>(occupied: &[bool], factor: impl Fn(usize) -> T) -> Vec<T>
+where
+    T: Copy + Zero + One,
+{
+    let mut offset = T::zero();
+    occupied
+        .iter()
+        .enumerate()
+        .map(|(i, &occupied)| {
+            let start = offset;
+            offset = offset + if occupied { T::one() } else { factor(i) };
+            start
+        })
+        .collect()
+}
+
+///
+/// Sum of `|a - b|` over every unordered pair of `values`
+///
+/// Once sorted, the value at index `i` is at least each of the `i` values
+/// before it, so it adds `i * value` and takes away the sum of those
+/// before it. Sorting makes this `O(n log n)` rather than `O(n^2)`.
+///
+pub fn pairwise_sum<T>(values: &mut [T]) -> T
+where
+    T: Copy + Ord + Zero + Sub<Output = T> + Mul<Output = T> + From<i64>,
+{
+    values.sort_unstable();
+
+    let mut before = T::zero();
+    let mut sum = T::zero();
+    for (i, &value) in values.iter().enumerate() {
+        sum = sum + T::from(i as i64) * value - before;
+        before = before + value;
+    }
+
+    sum
+}
+
+/// Sum of Manhattan distances over every unordered pair of `points`, one axis at a time
+pub fn distance_sum<T>(points: &[Point<T>]) -> T
+where
+    T: Copy + Ord + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + From<i64>,
+{
+    let mut xs: Vec<T> = points.iter().map(|point| point.x).collect();
+    let mut ys: Vec<T> = points.iter().map(|point| point.y).collect();
+
+    pairwise_sum(&mut xs) + pairwise_sum(&mut ys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn expands_galaxies() {
+        let map = GalaxyMap::parse(INPUT);
+        let expanded = map.expand(2);
+
+        assert_eq!(9, map.galaxies.len());
+        assert_eq!(Point { x: 4, y: 0 }, expanded[0]);
+        assert_eq!(Point { x: 5, y: 11 }, expanded[8]);
+        assert_eq!(374, distance_sum(&expanded));
+        assert_eq!(8410, distance_sum(&map.expand(100)));
+    }
+
+    #[test]
+    fn expands_axes_separately() {
+        let map = GalaxyMap::parse(INPUT);
+        let factor = |value: i64| Factor::from(value);
+
+        // matches the whole-number path when every factor is the same
+        let uniform = map.expand_with(&Expansion::uniform(10));
+        assert_eq!(Factor::from(1030), distance_sum(&uniform));
+
+        // columns alone grow, rows 3 and 7 vanish
+        let mut expansion = Expansion::new(factor(1), factor(2));
+        expansion.rows.lines.insert(3, factor(0));
+        expansion.rows.lines.insert(7, factor(0));
+        let expanded = map.expand_with(&expansion);
+        assert_eq!(
+            Point {
+                x: factor(5),
+                y: factor(7)
+            },
+            expanded[8]
+        );
+
+        // half-width empty lines land galaxies between whole tiles
+        let expanded = map.expand_with(&Expansion::new(Factor::new(1, 2), factor(1)));
+        assert_eq!(
+            Point {
+                x: factor(6),
+                y: Factor::new(7, 2)
+            },
+            expanded[3]
+        );
+    }
+
+    #[test]
+    fn matches_every_pair() {
+        // scattered but repeatable points, a few thousand pairs
+        let points: Vec<Point> = (0..100i64)
+            .map(|i| Point {
+                x: (i * 7919) % 211,
+                y: (i * 104_729) % 197,
+            })
+            .collect();
+
+        let brute: i64 = points
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| points[i + 1..].iter().map(move |b| (a, b)))
+            .map(|(a, b)| (a.x - b.x).abs() + (a.y - b.y).abs())
+            .sum();
+
+        assert_eq!(brute, distance_sum(&points));
+    }
+
+    #[test]
+    fn handles_many_galaxies() {
+        let points: Vec<Point> = (0..50_000i64).map(|i| Point { x: i, y: 0 }).collect();
+
+        // the gap after the `i`th point is crossed by `i * (n - i)` pairs, `(n^3 - n) / 6` in total
+        let n = 50_000i64;
+        assert_eq!((n * n * n - n) / 6, distance_sum(&points));
+    }
+}
@@ -1,2 +1,5 @@
+pub mod distances;
+pub mod expansion;
+pub mod galaxy;
 pub mod part1;
 pub mod part2;
@@ -0,0 +1,200 @@
+use crate::report::Report;
+use std::{borrow::Cow, ops::Deref};
+
+const BOM: char = '\u{feff}';
+
+/// Normalised input text and what it took to get there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized<'a> {
+    /// Borrows the original input when nothing needed changing
+    pub text: Cow<'a, str>,
+    pub report: Report,
+}
+
+impl<'a> Normalized<'a> {
+    /// Prints the report to stderr when anything changed, so fixed-up input doesn't go unnoticed
+    pub fn warn(self) -> Normalized<'a> {
+        if !self.report.is_clean() {
+            eprintln!("warning: normalised input: {}", self.report);
+        }
+        self
+    }
+}
+
+impl Deref for Normalized<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+///
+/// Puts line-based puzzle input into one canonical form
+///
+/// - no byte order mark
+/// - `\n` line endings, and exactly one after the last line
+/// - no trailing whitespace on any line
+/// - no blank lines at the start or end, and never two in a row, so blank
+///   lines only ever separate sections
+///
+/// Leading whitespace is kept, as are blank-line-separated sections.
+///
+pub fn text(input: &str) -> Normalized<'_> {
+    normalize(input, None)
+}
+
+///
+/// Same as [`text`], then pads short rows with `fill`
+///
+/// Each block of lines between blank lines is padded to its own widest row,
+/// so inputs holding several grids of different sizes keep them apart.
+///
+pub fn grid(input: &str, fill: char) -> Normalized<'_> {
+    normalize(input, Some(fill))
+}
+
+fn normalize(input: &str, fill: Option<char>) -> Normalized<'_> {
+    let mut report = Report::default();
+
+    let body = match input.strip_prefix(BOM) {
+        Some(body) => {
+            report.bom = true;
+            body
+        }
+        None => input,
+    };
+    report.crlf = body.matches("\r\n").count();
+
+    let mut lines: Vec<&str> = vec![];
+    for line in body.lines() {
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            report.trailing_whitespace += 1;
+        }
+
+        let is_blank = trimmed.is_empty();
+        let after_blank = lines.last().is_none_or(|last| last.is_empty());
+        if is_blank && after_blank {
+            report.blank_lines += 1;
+        } else {
+            lines.push(trimmed);
+        }
+    }
+    if lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+        report.blank_lines += 1;
+    }
+    report.final_newline = !lines.is_empty() && !body.ends_with('\n');
+
+    let widths: Vec<usize> = match fill {
+        Some(_) => block_widths(&lines),
+        None => vec![],
+    };
+    report.padded_rows = lines
+        .iter()
+        .zip(&widths)
+        .filter(|&(line, &width)| line.chars().count() < width)
+        .count();
+
+    if report.is_clean() {
+        return Normalized {
+            text: Cow::Borrowed(input),
+            report,
+        };
+    }
+
+    let mut text = String::with_capacity(body.len() + 1);
+    for (i, line) in lines.iter().enumerate() {
+        text.push_str(line);
+        if let (Some(fill), Some(&width)) = (fill, widths.get(i)) {
+            let padding = width - line.chars().count();
+            text.extend(std::iter::repeat_n(fill, padding));
+        }
+        text.push('\n');
+    }
+
+    Normalized {
+        text: Cow::Owned(text),
+        report,
+    }
+}
+
+/// Width of the widest row in the block of non-blank lines holding each line
+fn block_widths(lines: &[&str]) -> Vec<usize> {
+    let mut widths = Vec::with_capacity(lines.len());
+
+    for block in lines.split(|line| line.is_empty()) {
+        let width = block
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        widths.extend(std::iter::repeat_n(width, block.len()));
+        // the blank line after the block, if any, needs no padding
+        widths.push(0);
+    }
+    widths.truncate(lines.len());
+
+    widths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrows_clean_input() {
+        let input = "seeds: 1 2\n\nmap:\n3 4 5\n";
+        let normalized = text(input);
+
+        assert!(matches!(normalized.text, Cow::Borrowed(_)));
+        assert!(normalized.report.is_clean());
+        assert_eq!("", &*text(""));
+    }
+
+    #[test]
+    fn normalizes_text() {
+        let input = "\u{feff}\r\nseeds: 1 2  \r\n\r\n\r\nmap:\t\r\n3 4 5\r\n\r\n";
+        let normalized = text(input);
+
+        assert_eq!("seeds: 1 2\n\nmap:\n3 4 5\n", &*normalized);
+        assert_eq!(
+            Report {
+                bom: true,
+                crlf: 7,
+                trailing_whitespace: 2,
+                blank_lines: 3,
+                final_newline: false,
+                padded_rows: 0,
+            },
+            normalized.report
+        );
+        assert_eq!(
+            "removed byte order mark, converted 7 CRLF line endings, \
+             trimmed trailing whitespace from 2 lines, dropped 3 blank lines",
+            normalized.report.to_string()
+        );
+    }
+
+    #[test]
+    fn adds_final_newline() {
+        let normalized = text("a\nb");
+
+        assert_eq!("a\nb\n", &*normalized);
+        assert!(normalized.report.final_newline);
+        assert_eq!("added final newline", normalized.report.to_string());
+    }
+
+    #[test]
+    fn pads_each_grid_block() {
+        let input = "#..\n#\n.#.#\n\n##\n.\n";
+        let normalized = grid(input, '.');
+
+        assert_eq!("#...\n#...\n.#.#\n\n##\n..\n", &*normalized);
+        assert_eq!(3, normalized.report.padded_rows);
+
+        // text keeps ragged rows as they are
+        assert_eq!(input, &*text(input));
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+///
+/// What normalising an input changed
+///
+/// Every count is of lines, so a clean report means the input was already
+/// in the canonical form and came back untouched.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Leading byte order mark removed
+    pub bom: bool,
+    /// `\r\n` endings turned into `\n`
+    pub crlf: usize,
+    /// Lines with trailing spaces, tabs or stray `\r` trimmed
+    pub trailing_whitespace: usize,
+    /// Blank lines dropped from the start, the end, or runs of more than one
+    pub blank_lines: usize,
+    /// Final newline added because the input stopped mid-line
+    pub final_newline: bool,
+    /// Grid rows padded out to the width of the widest row in their block
+    pub padded_rows: usize,
+}
+
+impl Report {
+    pub fn is_clean(&self) -> bool {
+        *self == Report::default()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "input unchanged");
+        }
+
+        let mut changes = vec![];
+        if self.bom {
+            changes.push("removed byte order mark".to_string());
+        }
+        if self.crlf > 0 {
+            changes.push(format!("converted {} CRLF line endings", self.crlf));
+        }
+        if self.trailing_whitespace > 0 {
+            changes.push(format!(
+                "trimmed trailing whitespace from {} lines",
+                self.trailing_whitespace
+            ));
+        }
+        if self.blank_lines > 0 {
+            changes.push(format!("dropped {} blank lines", self.blank_lines));
+        }
+        if self.final_newline {
+            changes.push("added final newline".to_string());
+        }
+        if self.padded_rows > 0 {
+            changes.push(format!("padded {} grid rows", self.padded_rows));
+        }
+
+        write!(f, "{}", changes.join(", "))
+    }
+}